@@ -1,16 +1,468 @@
-use crate::pda::find_rooster_pda;
+use mpl_token_metadata::{
+    pda::{find_master_edition_account, find_metadata_account, find_token_record_account},
+    state::{TokenDelegateRole, TokenMetadataAccount, TokenRecord},
+};
+use solana_program::{program_option::COption, program_pack::Pack};
+
+use crate::{
+    pda::{
+        find_associated_token_account, find_indexed_rooster_pda, find_withdraw_request_pda,
+        WITHDRAW_REQUEST_PREFIX,
+    },
+    state::{Rooster, WithdrawRequest, MAX_MULTISIG_SIGNERS},
+    MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+};
 
 use super::*;
 
+/// Asserts that the account is the rooster PDA derived from the `seed` key (the authority
+/// that created the rooster) and vault `index`.
+pub fn assert_rooster_derivation(
+    account_info: &AccountInfo,
+    seed: &Pubkey,
+    index: Option<u64>,
+) -> Result<u8, ProgramError> {
+    let (pubkey, bump) = find_indexed_rooster_pda(seed, index);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::RoosterPDAInvalid.into());
+    }
+
+    Ok(bump)
+}
+
+/// Asserts that the account is the PDA described by the seeds stored in the rooster state.
 pub fn assert_rooster_pda(
     account_info: &AccountInfo,
-    authority_info: &AccountInfo,
+    rooster: &Rooster,
 ) -> Result<u8, ProgramError> {
-    let (pubkey, bump) = find_rooster_pda(authority_info.key);
+    let seeds = rooster.seeds();
+    let pubkey = Pubkey::create_program_address(&seeds.seeds(), &crate::ID)
+        .map_err(|_| Crows::RoosterPDAInvalid)?;
 
     if pubkey != *account_info.key {
         return Err(Crows::RoosterPDAInvalid.into());
     }
 
+    Ok(rooster.bump)
+}
+
+/// Asserts that the account is the current rooster authority and signed the transaction.
+pub fn assert_rooster_authority(rooster: &Rooster, authority_info: &AccountInfo) -> ProgramResult {
+    assert_signer(authority_info, Crows::AuthorityNotSigner)?;
+
+    if rooster.authority != *authority_info.key {
+        return Err(Crows::InvalidAuthority.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the operation guarded by the `PAUSE_*` flag is not paused on the rooster.
+pub fn assert_not_paused(rooster: &Rooster, flag: u8) -> ProgramResult {
+    if rooster.is_paused(flag) {
+        msg!("Rooster pause flags: {:#010b}", rooster.pause_flags);
+        return Err(Crows::Paused.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that enough multisig signers approved the operation, when the rooster is in
+/// multisig mode. Signers are read from `signer_infos`, usually the remaining accounts.
+pub fn assert_multisig(rooster: &Rooster, signer_infos: &[AccountInfo]) -> ProgramResult {
+    if rooster.threshold == 0 {
+        return Ok(());
+    }
+
+    let mut approvals: Vec<&Pubkey> = Vec::with_capacity(rooster.signers.len());
+
+    for signer_info in signer_infos {
+        if !signer_info.is_signer || !rooster.signers.contains(signer_info.key) {
+            continue;
+        }
+
+        if approvals.contains(&signer_info.key) {
+            return Err(Crows::DuplicateSigner.into());
+        }
+
+        approvals.push(signer_info.key);
+    }
+
+    if approvals.len() < rooster.threshold as usize {
+        msg!(
+            "Multisig approvals: {} of {} required",
+            approvals.len(),
+            rooster.threshold
+        );
+        return Err(Crows::NotEnoughSigners.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that a multisig configuration is valid: no duplicated keys, at most
+/// `MAX_MULTISIG_SIGNERS` keys and a threshold no greater than the number of keys.
+pub fn assert_multisig_config(signers: &[Pubkey], threshold: u8) -> ProgramResult {
+    if signers.len() > MAX_MULTISIG_SIGNERS || threshold as usize > signers.len() {
+        return Err(Crows::InvalidMultisigConfig.into());
+    }
+
+    if threshold == 0 && !signers.is_empty() {
+        return Err(Crows::InvalidMultisigConfig.into());
+    }
+
+    for (i, signer) in signers.iter().enumerate() {
+        if signers[i + 1..].contains(signer) {
+            return Err(Crows::DuplicateSigner.into());
+        }
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the PDA of the withdrawal of `mint` queued on the rooster.
+pub fn assert_withdraw_request_derivation(
+    account_info: &AccountInfo,
+    rooster: &Pubkey,
+    mint: &Pubkey,
+) -> Result<u8, ProgramError> {
+    let (pubkey, bump) = find_withdraw_request_pda(rooster, mint);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::InvalidWithdrawRequest.into());
+    }
+
     Ok(bump)
 }
+
+/// Asserts that the withdraw request account was queued on the rooster for `mint`.
+pub fn assert_withdraw_request(
+    account_info: &AccountInfo,
+    withdraw_request: &WithdrawRequest,
+    rooster: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    if withdraw_request.rooster != *rooster || withdraw_request.mint != *mint {
+        return Err(Crows::InvalidWithdrawRequest.into());
+    }
+
+    let pubkey = Pubkey::create_program_address(
+        &[
+            WITHDRAW_REQUEST_PREFIX,
+            rooster.as_ref(),
+            mint.as_ref(),
+            &[withdraw_request.bump],
+        ],
+        &crate::ID,
+    )
+    .map_err(|_| Crows::InvalidWithdrawRequest)?;
+
+    if pubkey != *account_info.key {
+        return Err(Crows::InvalidWithdrawRequest.into());
+    }
+
+    Ok(())
+}
+
+pub fn assert_legacy_bump(legacy_bump: Option<u8>, bump: u8) -> ProgramResult {
+    match legacy_bump {
+        Some(legacy_bump) if legacy_bump != bump => Err(Crows::InvalidBump.into()),
+        _ => Ok(()),
+    }
+}
+
+/// Asserts that the account is owned by `owner`.
+pub fn assert_owned_by(account_info: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account_info.owner != owner {
+        msg!("Account {} is not owned by {}", account_info.key, owner);
+        return Err(Crows::InvalidAccountOwner.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the program `program_id`.
+pub fn assert_program_id(account_info: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+    if account_info.key != program_id {
+        msg!("Expected program {}, got {}", program_id, account_info.key);
+        return Err(Crows::InvalidProgramId.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the instructions sysvar.
+pub fn assert_sysvar_instructions(account_info: &AccountInfo) -> ProgramResult {
+    if *account_info.key != solana_program::sysvar::instructions::ID {
+        return Err(Crows::InvalidSysvar.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account signed the transaction, failing with the role-specific `error`.
+pub fn assert_signer(account_info: &AccountInfo, error: Crows) -> ProgramResult {
+    if !account_info.is_signer {
+        return Err(error.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is writable. Absent optional accounts, replaced by the Token
+/// Metadata program, are exempt since the runtime never passes programs as writable.
+pub fn assert_writable(account_info: &AccountInfo) -> ProgramResult {
+    if !account_info.is_writable && *account_info.key != mpl_token_metadata::ID {
+        msg!("Account {} must be writable", account_info.key);
+        return Err(Crows::AccountNotWritable.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is a token account owned by `owner`.
+pub fn assert_token_account_owner(
+    account_info: &AccountInfo,
+    owner: &Pubkey,
+) -> Result<spl_token::state::Account, ProgramError> {
+    assert_owned_by(account_info, &spl_token::ID)?;
+
+    let token = spl_token::state::Account::unpack(&account_info.try_borrow_data()?)?;
+
+    if token.owner != *owner {
+        return Err(Crows::InvalidTokenAccount.into());
+    }
+
+    Ok(token)
+}
+
+/// Asserts that the account is a token account of `mint` owned by `owner`.
+pub fn assert_token_account(
+    account_info: &AccountInfo,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Result<spl_token::state::Account, ProgramError> {
+    let token = assert_token_account_owner(account_info, owner)?;
+
+    if token.mint != *mint {
+        return Err(Crows::MintMismatch.into());
+    }
+
+    Ok(token)
+}
+
+/// Asserts that the account is a token account of `mint` the rooster PDA can burn: either
+/// owned by the PDA, or delegated to it with a Utility token record.
+pub fn assert_burnable_token_account(
+    account_info: &AccountInfo,
+    token_record_info: &AccountInfo,
+    mint: &Pubkey,
+    rooster_pda: &Pubkey,
+) -> Result<spl_token::state::Account, ProgramError> {
+    assert_owned_by(account_info, &spl_token::ID)?;
+
+    let token = spl_token::state::Account::unpack(&account_info.try_borrow_data()?)?;
+
+    if token.mint != *mint {
+        return Err(Crows::MintMismatch.into());
+    }
+
+    if token.owner == *rooster_pda {
+        return Ok(token);
+    }
+
+    // only programmable tokens have a Utility delegate, recorded in their token record
+    if token.delegate != COption::Some(*rooster_pda)
+        || *token_record_info.key == mpl_token_metadata::ID
+    {
+        return Err(Crows::InvalidTokenAccount.into());
+    }
+
+    assert_owned_by(token_record_info, &mpl_token_metadata::ID)?;
+
+    let token_record = TokenRecord::from_account_info(token_record_info)?;
+
+    if token_record.delegate != Some(*rooster_pda)
+        || token_record.delegate_role != Some(TokenDelegateRole::Utility)
+    {
+        return Err(Crows::InvalidTokenAccount.into());
+    }
+
+    Ok(token)
+}
+
+/// Asserts that the account is the associated token account of `owner` for `mint`. Used for
+/// destinations that Token Metadata creates on demand.
+pub fn assert_associated_token_account(
+    account_info: &AccountInfo,
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> ProgramResult {
+    let (pubkey, _) = find_associated_token_account(owner, mint, &spl_token::ID);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::InvalidTokenAccount.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the metadata PDA of `mint`.
+pub fn assert_metadata_derivation(account_info: &AccountInfo, mint: &Pubkey) -> ProgramResult {
+    let (pubkey, _) = find_metadata_account(mint);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::MetadataMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the (master or print) edition PDA of `mint`.
+pub fn assert_edition_derivation(account_info: &AccountInfo, mint: &Pubkey) -> ProgramResult {
+    let (pubkey, _) = find_master_edition_account(mint);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::EditionMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is the token record PDA of `token` for `mint`.
+pub fn assert_token_record_derivation(
+    account_info: &AccountInfo,
+    mint: &Pubkey,
+    token: &Pubkey,
+) -> ProgramResult {
+    let (pubkey, _) = find_token_record_account(mint, token);
+
+    if pubkey != *account_info.key {
+        return Err(Crows::TokenRecordMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Asserts that the account is a rule set owned by the Token Auth Rules program, unless the
+/// Token Metadata program is passed in its place.
+pub fn assert_rule_set(account_info: &AccountInfo) -> ProgramResult {
+    if *account_info.key == mpl_token_metadata::ID {
+        return Ok(());
+    }
+
+    assert_owned_by(account_info, &MPL_TOKEN_AUTH_RULES_PROGRAM_ID)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rooster(signers: &[Pubkey], threshold: u8) -> Rooster {
+        let mut rooster = Rooster::new(255, Pubkey::new_unique(), None, 0);
+        rooster.signers = signers.to_vec();
+        rooster.threshold = threshold;
+        rooster
+    }
+
+    fn check(rooster: &Rooster, signers: &[(Pubkey, bool)]) -> ProgramResult {
+        let owner = Pubkey::default();
+        let mut lamports = vec![0; signers.len()];
+        let mut data = vec![[0u8; 0]; signers.len()];
+        let infos: Vec<AccountInfo> = signers
+            .iter()
+            .zip(lamports.iter_mut())
+            .zip(data.iter_mut())
+            .map(|(((key, is_signer), lamports), data)| {
+                AccountInfo::new(key, *is_signer, false, lamports, data, &owner, false, 0)
+            })
+            .collect();
+
+        assert_multisig(rooster, &infos)
+    }
+
+    #[test]
+    fn multisig_threshold() {
+        let members = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+        let rooster = rooster(&members, 2);
+
+        assert_eq!(
+            check(&rooster, &[(members[0], true), (members[2], true)]),
+            Ok(())
+        );
+        assert_eq!(
+            check(&rooster, &[(members[1], true)]),
+            Err(Crows::NotEnoughSigners.into())
+        );
+    }
+
+    #[test]
+    fn multisig_ignores_non_members_and_non_signers() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let rooster = rooster(&members, 2);
+
+        assert_eq!(
+            check(
+                &rooster,
+                &[(members[0], true), (Pubkey::new_unique(), true)]
+            ),
+            Err(Crows::NotEnoughSigners.into())
+        );
+        assert_eq!(
+            check(&rooster, &[(members[0], true), (members[1], false)]),
+            Err(Crows::NotEnoughSigners.into())
+        );
+    }
+
+    #[test]
+    fn multisig_rejects_duplicate_signers() {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique()];
+        let rooster = rooster(&members, 2);
+
+        assert_eq!(
+            check(&rooster, &[(members[0], true), (members[0], true)]),
+            Err(Crows::DuplicateSigner.into())
+        );
+    }
+
+    #[test]
+    fn multisig_disabled() {
+        assert_eq!(check(&rooster(&[], 0), &[]), Ok(()));
+    }
+
+    #[test]
+    fn multisig_config() {
+        let members: Vec<Pubkey> = (0..MAX_MULTISIG_SIGNERS + 1)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+
+        assert_eq!(assert_multisig_config(&[], 0), Ok(()));
+        assert_eq!(assert_multisig_config(&members[..3], 2), Ok(()));
+        assert_eq!(
+            assert_multisig_config(&members[..MAX_MULTISIG_SIGNERS], 1),
+            Ok(())
+        );
+
+        assert_eq!(
+            assert_multisig_config(&members[..3], 0),
+            Err(Crows::InvalidMultisigConfig.into())
+        );
+        assert_eq!(
+            assert_multisig_config(&members[..3], 4),
+            Err(Crows::InvalidMultisigConfig.into())
+        );
+        assert_eq!(
+            assert_multisig_config(&members, 1),
+            Err(Crows::InvalidMultisigConfig.into())
+        );
+        assert_eq!(
+            assert_multisig_config(&[members[0], members[1], members[0]], 2),
+            Err(Crows::DuplicateSigner.into())
+        );
+    }
+}
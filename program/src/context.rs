@@ -0,0 +1,642 @@
+use crate::assertions::{assert_signer, assert_writable};
+
+use super::*;
+
+/// Declares the account layout of an instruction once and generates from it both the
+/// on-chain context, which parses the accounts and checks signers and writable accounts, and
+/// the client-side keys, which build the account metas in the same order. Each account is
+/// `readonly`, `writable`, `signer` or `writable_signer`; signers name the `Crows` error raised
+/// when they did not sign, or none when the handler checks the signer itself. The layouts are
+/// tested against the Shank `#[account(..)]` annotations of `RoosterCommand`.
+macro_rules! account_context {
+    (
+        $(#[$doc:meta])*
+        $name:ident, $keys:ident {
+            $($field:ident: $access:ident $(($error:ident))?),* $(,)?
+        }
+    ) => {
+        $(#[$doc])*
+        pub struct $name<'a, 'info> {
+            $(pub $field: &'a AccountInfo<'info>,)*
+            /// Accounts following the declared ones, such as the multisig signers.
+            pub remaining: &'a [AccountInfo<'info>],
+        }
+
+        impl<'a, 'info> $name<'a, 'info> {
+            /// Parses the accounts of the instruction, checking signers and writable accounts.
+            pub fn context(accounts: &'a [AccountInfo<'info>]) -> Result<Self, ProgramError> {
+                let account_iter = &mut accounts.iter();
+                $(
+                    let $field = next_account_info(account_iter)?;
+                    account_context!(@check $access, $field $(, $error)?);
+                )*
+
+                Ok(Self {
+                    $($field,)*
+                    remaining: account_iter.as_slice(),
+                })
+            }
+        }
+
+        #[doc = concat!("Keys of the [`", stringify!($name), "`] layout, used by the instruction builders.")]
+        #[derive(Clone, Debug, Default, PartialEq, Eq)]
+        pub struct $keys {
+            $(pub $field: Pubkey,)*
+        }
+
+        impl $keys {
+            /// Names of the accounts, in the order the program parses them.
+            pub const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            /// Account metas of the instruction, in the order the program parses them.
+            pub fn to_account_metas(&self) -> Vec<AccountMeta> {
+                vec![$(account_context!(@meta $access, self.$field)),*]
+            }
+        }
+    };
+
+    (@check readonly, $info:ident) => {};
+    (@check writable, $info:ident) => {
+        assert_writable($info)?;
+    };
+    (@check signer, $info:ident) => {};
+    (@check signer, $info:ident, $error:ident) => {
+        assert_signer($info, Crows::$error)?;
+    };
+    (@check writable_signer, $info:ident, $error:ident) => {
+        assert_writable($info)?;
+        assert_signer($info, Crows::$error)?;
+    };
+
+    (@meta readonly, $key:expr) => {
+        AccountMeta::new_readonly($key, false)
+    };
+    // absent optional accounts are replaced by the Token Metadata program, which the runtime
+    // only accepts as writable when it is invoked
+    (@meta writable, $key:expr) => {
+        if $key == mpl_token_metadata::ID {
+            AccountMeta::new_readonly($key, false)
+        } else {
+            AccountMeta::new($key, false)
+        }
+    };
+    (@meta signer, $key:expr) => {
+        AccountMeta::new_readonly($key, true)
+    };
+    (@meta writable_signer, $key:expr) => {
+        AccountMeta::new($key, true)
+    };
+}
+
+account_context! {
+    /// Accounts of `Init`.
+    InitAccounts, InitKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        system_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Withdraw`, also used by `ExecuteWithdraw` after the withdraw request.
+    WithdrawAccounts, WithdrawKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        token: writable,
+        destination_owner: readonly,
+        destination: writable,
+        mint: writable,
+        metadata: writable,
+        edition: writable,
+        owner_token_record: writable,
+        destination_token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        spl_ata_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Delegate` and `DeprecatedDelegate`.
+    DelegateAccounts, DelegateKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        delegate: readonly,
+        rooster_pda: writable,
+        token: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Lock` and `Unlock` (and their deprecated variants).
+    LockAccounts, LockKeys {
+        delegate: readonly,
+        token_owner: writable_signer(TokenOwnerNotSigner),
+        token: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `ProgrammableLock` and `ProgrammableUnlock` (and their deprecated variants).
+    ProgrammableLockAccounts, ProgrammableLockKeys {
+        delegate: readonly,
+        token_owner: writable_signer(TokenOwnerNotSigner),
+        token: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `DelegateTransfer`.
+    DelegateTransferAccounts, DelegateTransferKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        source_owner: writable,
+        source_token: writable,
+        destination_owner: readonly,
+        destination_token: writable,
+        mint: writable,
+        metadata: writable,
+        edition: writable,
+        source_token_record: writable,
+        destination_token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        spl_ata_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Deposit`.
+    DepositAccounts, DepositKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        token: writable,
+        destination: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        owner_token_record: writable,
+        destination_token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        spl_ata_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `MigrateRooster`.
+    MigrateRoosterAccounts, MigrateRoosterKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        system_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Close`, followed by the token accounts of the rooster PDA and the
+    /// multisig signers.
+    CloseAccounts, CloseKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        recipient: writable,
+        spl_token_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of the authority-only configuration commands: `ProposeAuthority`,
+    /// `SetMultisig`, `SetWithdrawDelay` and `SetGuardian`.
+    RoosterAuthorityAccounts, RoosterAuthorityKeys {
+        authority: signer(AuthorityNotSigner),
+        rooster_pda: writable,
+    }
+}
+
+account_context! {
+    /// Accounts of `AcceptAuthority`.
+    AcceptAuthorityAccounts, AcceptAuthorityKeys {
+        new_authority: signer(PendingAuthorityNotSigner),
+        rooster_pda: writable,
+    }
+}
+
+account_context! {
+    /// Accounts of `SetPause`. The signer is the authority or the guardian, checked by the
+    /// handler once the rooster is loaded.
+    SetPauseAccounts, SetPauseKeys {
+        authority: signer,
+        rooster_pda: writable,
+    }
+}
+
+account_context! {
+    /// Accounts of `QueueWithdraw`.
+    QueueWithdrawAccounts, QueueWithdrawKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        withdraw_request: writable,
+        mint: readonly,
+        destination_owner: readonly,
+        destination: readonly,
+        system_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `CancelWithdraw`.
+    CancelWithdrawAccounts, CancelWithdrawKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        withdraw_request: writable,
+    }
+}
+
+account_context! {
+    /// Accounts of `Revoke`.
+    RevokeAccounts, RevokeKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: readonly,
+        delegate: readonly,
+        token: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Burn`, followed by the multisig signers.
+    BurnAccounts, BurnKeys {
+        authority: signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        collection_metadata: writable,
+        metadata: writable,
+        edition: writable,
+        mint: writable,
+        token: writable,
+        master_edition: writable,
+        master_edition_mint: readonly,
+        master_edition_token: readonly,
+        edition_marker: writable,
+        token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `CreateProgrammable`.
+    CreateProgrammableAccounts, CreateProgrammableKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        mint: writable_signer(MintNotSigner),
+        metadata: writable,
+        edition: writable,
+        token: writable,
+        token_record: writable,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        spl_token_program: readonly,
+        spl_ata_program: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `UpdateMetadata`.
+    UpdateMetadataAccounts, UpdateMetadataKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: readonly,
+        delegate_record: readonly,
+        token: readonly,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `DelegateMetadata` and `RevokeMetadata`.
+    MetadataDelegateAccounts, MetadataDelegateKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: readonly,
+        delegate: readonly,
+        delegate_record: writable,
+        mint: readonly,
+        metadata: writable,
+        edition: readonly,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+        authorization_rules_program: readonly,
+        authorization_rules: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `Verify` and `Unverify`.
+    VerificationAccounts, VerificationKeys {
+        authority: signer(AuthorityNotSigner),
+        rooster_pda: readonly,
+        metadata: writable,
+        collection_mint: readonly,
+        collection_metadata: writable,
+        collection_master_edition: readonly,
+        token_metadata_program: readonly,
+        system_program: readonly,
+        sysvar_instructions: readonly,
+    }
+}
+
+account_context! {
+    /// Accounts of `PrintEdition`, followed by the multisig signers.
+    PrintEditionAccounts, PrintEditionKeys {
+        authority: writable_signer(AuthorityNotSigner),
+        rooster_pda: writable,
+        edition_mint: writable_signer(MintNotSigner),
+        edition_metadata: writable,
+        edition: writable,
+        edition_token_owner: readonly,
+        edition_token: writable,
+        edition_token_record: writable,
+        master_edition: writable,
+        edition_marker: writable,
+        master_token: readonly,
+        master_metadata: readonly,
+        update_authority: readonly,
+        token_metadata_program: readonly,
+        spl_token_program: readonly,
+        spl_ata_program: readonly,
+        sysvar_instructions: readonly,
+        system_program: readonly,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Name, writable and signer flags of an account, as declared by a layout or by Shank.
+    type Account = (String, bool, bool);
+
+    /// Accounts of every `RoosterCommand` variant, parsed from its Shank `#[account(..)]`
+    /// attributes in `instruction.rs`.
+    fn shank_accounts() -> Vec<(String, Vec<Account>)> {
+        let source = include_str!("instruction.rs");
+        let start = source.find("pub enum RoosterCommand {").unwrap();
+        let mut variants = Vec::new();
+        let mut accounts = Vec::new();
+
+        for line in source[start..].lines().skip(1) {
+            let line = line.trim();
+
+            if line == "}" {
+                break;
+            } else if let Some(attribute) = line.strip_prefix("#[account(") {
+                let (flags, rest) = attribute.split_once("name=\"").unwrap();
+                let name = rest.split('"').next().unwrap().to_string();
+                let flags: Vec<&str> = flags.split(',').map(str::trim).collect();
+                accounts.push((name, flags.contains(&"writable"), flags.contains(&"signer")));
+            } else if !line.is_empty() && !line.starts_with("//") {
+                let variant = line.split(['(', ',']).next().unwrap();
+                variants.push((variant.to_string(), std::mem::take(&mut accounts)));
+            }
+        }
+
+        variants
+    }
+
+    /// Accounts of a layout, read from the metas built by its keys.
+    fn layout(names: &[&str], metas: Vec<AccountMeta>) -> Vec<Account> {
+        names
+            .iter()
+            .zip(metas)
+            .map(|(name, meta)| (name.to_string(), meta.is_writable, meta.is_signer))
+            .collect()
+    }
+
+    #[test]
+    fn layouts_match_shank_accounts() {
+        let withdraw = layout(
+            WithdrawKeys::NAMES,
+            WithdrawKeys::default().to_account_metas(),
+        );
+        let mut execute_withdraw = vec![("withdraw_request".to_string(), true, false)];
+        execute_withdraw.extend(withdraw.clone());
+
+        let delegate = layout(
+            DelegateKeys::NAMES,
+            DelegateKeys::default().to_account_metas(),
+        );
+        let lock = layout(LockKeys::NAMES, LockKeys::default().to_account_metas());
+        let programmable_lock = layout(
+            ProgrammableLockKeys::NAMES,
+            ProgrammableLockKeys::default().to_account_metas(),
+        );
+        let rooster_authority = layout(
+            RoosterAuthorityKeys::NAMES,
+            RoosterAuthorityKeys::default().to_account_metas(),
+        );
+        let metadata_delegate = layout(
+            MetadataDelegateKeys::NAMES,
+            MetadataDelegateKeys::default().to_account_metas(),
+        );
+        let verification = layout(
+            VerificationKeys::NAMES,
+            VerificationKeys::default().to_account_metas(),
+        );
+
+        let init = layout(InitKeys::NAMES, InitKeys::default().to_account_metas());
+
+        let expected = [
+            ("Init", init.clone()),
+            ("Withdraw", withdraw),
+            ("DeprecatedDelegate", delegate.clone()),
+            ("DeprecatedLock", lock.clone()),
+            ("DeprecatedUnlock", lock.clone()),
+            ("DeprecatedProgrammableLock", programmable_lock.clone()),
+            ("DeprecatedProgrammableUnlock", programmable_lock.clone()),
+            (
+                "DelegateTransfer",
+                layout(
+                    DelegateTransferKeys::NAMES,
+                    DelegateTransferKeys::default().to_account_metas(),
+                ),
+            ),
+            (
+                "Deposit",
+                layout(
+                    DepositKeys::NAMES,
+                    DepositKeys::default().to_account_metas(),
+                ),
+            ),
+            ("Delegate", delegate),
+            ("Lock", lock.clone()),
+            ("Unlock", lock),
+            ("ProgrammableLock", programmable_lock.clone()),
+            ("ProgrammableUnlock", programmable_lock),
+            (
+                "MigrateRooster",
+                layout(
+                    MigrateRoosterKeys::NAMES,
+                    MigrateRoosterKeys::default().to_account_metas(),
+                ),
+            ),
+            (
+                "Close",
+                layout(CloseKeys::NAMES, CloseKeys::default().to_account_metas()),
+            ),
+            ("ProposeAuthority", rooster_authority.clone()),
+            (
+                "AcceptAuthority",
+                layout(
+                    AcceptAuthorityKeys::NAMES,
+                    AcceptAuthorityKeys::default().to_account_metas(),
+                ),
+            ),
+            ("SetMultisig", rooster_authority.clone()),
+            ("SetWithdrawDelay", rooster_authority.clone()),
+            (
+                "QueueWithdraw",
+                layout(
+                    QueueWithdrawKeys::NAMES,
+                    QueueWithdrawKeys::default().to_account_metas(),
+                ),
+            ),
+            ("ExecuteWithdraw", execute_withdraw),
+            (
+                "CancelWithdraw",
+                layout(
+                    CancelWithdrawKeys::NAMES,
+                    CancelWithdrawKeys::default().to_account_metas(),
+                ),
+            ),
+            ("SetGuardian", rooster_authority),
+            (
+                "SetPause",
+                layout(
+                    SetPauseKeys::NAMES,
+                    SetPauseKeys::default().to_account_metas(),
+                ),
+            ),
+            (
+                "Revoke",
+                layout(RevokeKeys::NAMES, RevokeKeys::default().to_account_metas()),
+            ),
+            (
+                "Burn",
+                layout(BurnKeys::NAMES, BurnKeys::default().to_account_metas()),
+            ),
+            (
+                "CreateProgrammable",
+                layout(
+                    CreateProgrammableKeys::NAMES,
+                    CreateProgrammableKeys::default().to_account_metas(),
+                ),
+            ),
+            (
+                "UpdateMetadata",
+                layout(
+                    UpdateMetadataKeys::NAMES,
+                    UpdateMetadataKeys::default().to_account_metas(),
+                ),
+            ),
+            ("DelegateMetadata", metadata_delegate.clone()),
+            ("RevokeMetadata", metadata_delegate),
+            ("Verify", verification.clone()),
+            ("Unverify", verification),
+            (
+                "PrintEdition",
+                layout(
+                    PrintEditionKeys::NAMES,
+                    PrintEditionKeys::default().to_account_metas(),
+                ),
+            ),
+            ("InitIndexed", init),
+        ];
+
+        let shank = shank_accounts();
+        assert_eq!(
+            shank
+                .iter()
+                .map(|(variant, _)| variant.as_str())
+                .collect::<Vec<_>>(),
+            expected
+                .iter()
+                .map(|(variant, _)| *variant)
+                .collect::<Vec<_>>(),
+        );
+
+        for ((variant, accounts), (_, layout)) in shank.iter().zip(expected.iter()) {
+            assert_eq!(accounts, layout, "{} accounts", variant);
+        }
+    }
+
+    #[test]
+    fn absent_optional_accounts_are_readonly() {
+        let metas = BurnKeys {
+            collection_metadata: mpl_token_metadata::ID,
+            token_record: mpl_token_metadata::ID,
+            ..BurnKeys::default()
+        }
+        .to_account_metas();
+
+        assert!(!metas[2].is_writable);
+        assert!(metas[3].is_writable);
+        assert!(!metas[11].is_writable);
+    }
+}
@@ -1,26 +1,115 @@
+use mpl_token_auth_rules::error::RuleSetError;
+use mpl_token_metadata::error::MetadataError;
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive as _;
 use solana_program::{
     decode_error::DecodeError,
     msg,
     program_error::{PrintProgramError, ProgramError},
+    pubkey::Pubkey,
 };
 use thiserror::Error;
 
+/// Errors returned by the Rooster program. Codes are part of the program interface: existing
+/// codes never change and new variants take the next free code. Codes 33-36, 45 and 51-60
+/// are unassigned: failed CPIs abort the transaction with the error of the invoked program,
+/// and the authority pays for every account Rooster creates.
 #[derive(Error, Clone, Debug, Eq, PartialEq, FromPrimitive)]
 pub enum Crows {
-    /// Error description
     #[error("The 🐓 crows: Authority key is not a signer")]
-    NotASigner,
+    NotASigner = 0,
     #[error("The 🐓 crows: Invalid Rooster PDA derivation")]
-    RoosterPDAInvalid,
+    RoosterPDAInvalid = 1,
     #[error("The 🐓 crows: Transfer builder failed")]
-    TransferBuilderFailed,
+    TransferBuilderFailed = 2,
     #[error("The 🐓 crows: Delegate builder failed")]
-    DelegateBuilderFailed,
+    DelegateBuilderFailed = 3,
     #[error("The 🐓 crows: Lock builder failed")]
-    LockBuilderFailed,
+    LockBuilderFailed = 4,
     #[error("The 🐓 crows: Unlock builder failed")]
-    UnlockBuilderFailed,
+    UnlockBuilderFailed = 5,
+    #[error("The 🐓 crows: Bump does not match the Rooster PDA derivation")]
+    InvalidBump = 6,
+    #[error("The 🐓 crows: Account is not owned by the Rooster program")]
+    IncorrectOwner = 7,
+    #[error("The 🐓 crows: Account is not a Rooster account")]
+    InvalidAccountKey = 8,
+    #[error("The 🐓 crows: Rooster account must be migrated to the current version")]
+    RoosterNeedsMigration = 9,
+    #[error("The 🐓 crows: Token account is not owned by the Rooster PDA")]
+    InvalidTokenAccount = 10,
+    #[error("The 🐓 crows: Rooster still holds tokens")]
+    RoosterHoldsTokens = 11,
+    #[error("The 🐓 crows: Numerical overflow")]
+    NumericalOverflow = 12,
+    #[error("The 🐓 crows: Authority does not match the Rooster authority")]
+    InvalidAuthority = 13,
+    #[error("The 🐓 crows: Signer is not the pending Rooster authority")]
+    InvalidPendingAuthority = 14,
+    #[error("The 🐓 crows: Unsupported Rooster account version")]
+    UnsupportedVersion = 15,
+    #[error("The 🐓 crows: Multisig signer provided more than once")]
+    DuplicateSigner = 16,
+    #[error("The 🐓 crows: Not enough multisig signers")]
+    NotEnoughSigners = 17,
+    #[error("The 🐓 crows: Invalid multisig signer set or threshold")]
+    InvalidMultisigConfig = 18,
+    #[error("The 🐓 crows: Withdrawal is timelocked")]
+    WithdrawTimelocked = 19,
+    #[error("The 🐓 crows: Withdraw request does not match the withdrawal")]
+    InvalidWithdrawRequest = 20,
+    #[error("The 🐓 crows: Authorization data does not match the queued withdrawal")]
+    AuthorizationDataMismatch = 21,
+    #[error("The 🐓 crows: Withdraw delay must not be negative")]
+    InvalidWithdrawDelay = 22,
+    #[error("The 🐓 crows: Rooster is paused")]
+    Paused = 23,
+    #[error("The 🐓 crows: Invalid pause flags")]
+    InvalidPauseFlags = 24,
+    #[error("The 🐓 crows: Failed to build Revoke instruction")]
+    RevokeBuilderFailed = 25,
+    #[error("The 🐓 crows: Failed to build Burn instruction")]
+    BurnBuilderFailed = 26,
+    #[error("The 🐓 crows: Failed to build Create instruction")]
+    CreateBuilderFailed = 27,
+    #[error("The 🐓 crows: Failed to build Mint instruction")]
+    MintBuilderFailed = 28,
+    #[error("The 🐓 crows: Failed to build Update instruction")]
+    UpdateBuilderFailed = 29,
+    #[error("The 🐓 crows: Failed to build Verify instruction")]
+    VerifyBuilderFailed = 30,
+    #[error("The 🐓 crows: Failed to build Unverify instruction")]
+    UnverifyBuilderFailed = 31,
+    #[error("The 🐓 crows: Failed to build Print instruction")]
+    PrintBuilderFailed = 32,
+    #[error("The 🐓 crows: Account is not owned by the expected program")]
+    InvalidAccountOwner = 37,
+    #[error("The 🐓 crows: Program account does not match the expected program id")]
+    InvalidProgramId = 38,
+    #[error("The 🐓 crows: Account does not belong to the given mint")]
+    MintMismatch = 39,
+    #[error("The 🐓 crows: Token record does not match the token account")]
+    TokenRecordMismatch = 40,
+    #[error("The 🐓 crows: Metadata account does not match the mint")]
+    MetadataMismatch = 41,
+    #[error("The 🐓 crows: Edition account does not match the mint")]
+    EditionMismatch = 42,
+    #[error("The 🐓 crows: Authority must sign")]
+    AuthorityNotSigner = 43,
+    #[error("The 🐓 crows: Token owner must sign")]
+    TokenOwnerNotSigner = 44,
+    #[error("The 🐓 crows: New mint must sign")]
+    MintNotSigner = 46,
+    #[error("The 🐓 crows: Pending authority must sign")]
+    PendingAuthorityNotSigner = 47,
+    #[error("The 🐓 crows: Guardian must sign")]
+    GuardianNotSigner = 48,
+    #[error("The 🐓 crows: Account must be writable")]
+    AccountNotWritable = 49,
+    #[error("The 🐓 crows: Account is not a valid sysvar")]
+    InvalidSysvar = 50,
+    #[error("The 🐓 crows: Rooster has queued withdrawals")]
+    WithdrawalsQueued = 61,
 }
 
 impl PrintProgramError for Crows {
@@ -37,6 +126,121 @@ impl From<Crows> for ProgramError {
 
 impl<T> DecodeError<T> for Crows {
     fn type_of() -> &'static str {
-        "Error Thingy"
+        "Crows"
+    }
+}
+
+/// Custom error of Rooster or of one of the programs it invokes, decoded into its typed enum.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DecodedError {
+    Rooster(Crows),
+    TokenMetadata(MetadataError),
+    TokenAuthRules(RuleSetError),
+}
+
+impl DecodedError {
+    /// Decodes a `ProgramError::Custom` raised by `program_id`. Error codes overlap between
+    /// programs, so `program_id` must be the program that failed, as reported in the logs, which
+    /// for a failed CPI is the invoked program rather than Rooster.
+    pub fn decode(program_id: &Pubkey, error: &ProgramError) -> Option<Self> {
+        let code = match error {
+            ProgramError::Custom(code) => *code,
+            _ => return None,
+        };
+
+        if *program_id == crate::ID {
+            Crows::from_u32(code).map(Self::Rooster)
+        } else if *program_id == mpl_token_metadata::ID {
+            MetadataError::from_u32(code).map(Self::TokenMetadata)
+        } else if *program_id == crate::MPL_TOKEN_AUTH_RULES_PROGRAM_ID {
+            RuleSetError::from_u32(code).map(Self::TokenAuthRules)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_are_stable() {
+        assert_eq!(Crows::NotASigner as u32, 0);
+        assert_eq!(Crows::PrintBuilderFailed as u32, 32);
+        assert_eq!(Crows::InvalidAccountOwner as u32, 37);
+        assert_eq!(Crows::MintNotSigner as u32, 46);
+        assert_eq!(Crows::InvalidSysvar as u32, 50);
+        assert_eq!(Crows::WithdrawalsQueued as u32, 61);
+    }
+
+    #[test]
+    fn decodes_by_program_id() {
+        assert_eq!(
+            DecodedError::decode(&crate::ID, &Crows::Paused.into()),
+            Some(DecodedError::Rooster(Crows::Paused))
+        );
+        assert_eq!(
+            DecodedError::decode(&mpl_token_metadata::ID, &ProgramError::Custom(1)),
+            Some(DecodedError::TokenMetadata(
+                MetadataError::InstructionPackError
+            ))
+        );
+        assert_eq!(
+            DecodedError::decode(
+                &crate::MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+                &ProgramError::Custom(1)
+            ),
+            Some(DecodedError::TokenAuthRules(RuleSetError::DataTypeMismatch))
+        );
+    }
+
+    #[test]
+    fn overlapping_codes_depend_on_program_id() {
+        let error = ProgramError::Custom(0);
+
+        assert_eq!(
+            DecodedError::decode(&crate::ID, &error),
+            Some(DecodedError::Rooster(Crows::NotASigner))
+        );
+        assert_eq!(
+            DecodedError::decode(&mpl_token_metadata::ID, &error),
+            Some(DecodedError::TokenMetadata(
+                MetadataError::InstructionUnpackError
+            ))
+        );
+        assert_eq!(
+            DecodedError::decode(&crate::MPL_TOKEN_AUTH_RULES_PROGRAM_ID, &error),
+            Some(DecodedError::TokenAuthRules(
+                RuleSetError::NumericalOverflow
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_errors_are_not_decoded() {
+        // unknown program
+        assert_eq!(
+            DecodedError::decode(&spl_token::ID, &ProgramError::Custom(0)),
+            None
+        );
+        // not a custom error
+        assert_eq!(
+            DecodedError::decode(&crate::ID, &ProgramError::InvalidArgument),
+            None
+        );
+        // unassigned and out of range codes
+        assert_eq!(
+            DecodedError::decode(&crate::ID, &ProgramError::Custom(33)),
+            None
+        );
+        assert_eq!(
+            DecodedError::decode(&crate::ID, &ProgramError::Custom(u32::MAX)),
+            None
+        );
+        assert_eq!(
+            DecodedError::decode(&mpl_token_metadata::ID, &ProgramError::Custom(u32::MAX)),
+            None
+        );
     }
 }
@@ -1,39 +1,284 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use mpl_token_metadata::{
-    pda::{find_master_edition_account, find_metadata_account, find_token_record_account},
+    instruction::{MetadataDelegateRole, RuleSetToggle},
+    pda::{
+        find_master_edition_account, find_metadata_account, find_metadata_delegate_record_account,
+        find_token_record_account,
+    },
     processor::AuthorizationData,
+    state::{Creator, Data, PrintSupply},
 };
 use shank::ShankInstruction;
 
+use crate::{
+    context::{
+        AcceptAuthorityKeys, BurnKeys, CancelWithdrawKeys, CloseKeys, CreateProgrammableKeys,
+        DelegateKeys, DelegateTransferKeys, DepositKeys, InitKeys, LockKeys, MetadataDelegateKeys,
+        MigrateRoosterKeys, PrintEditionKeys, ProgrammableLockKeys, QueueWithdrawKeys, RevokeKeys,
+        RoosterAuthorityKeys, SetPauseKeys, UpdateMetadataKeys, VerificationKeys, WithdrawKeys,
+    },
+    pda::{find_associated_token_account, find_edition_marker_account, find_withdraw_request_pda},
+};
+
 use super::*;
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct InitArgs {
+    /// Vault index of the rooster, `None` for the authority's unindexed rooster.
+    pub index: Option<u64>,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub struct WithdrawArgs {
     pub auth_data: AuthorizationData,
 }
 
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DepositArgs {
+    pub auth_data: AuthorizationData,
+}
+
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub struct DelegateArgs {
     pub amount: u64,
-    pub authority: Pubkey,
-    pub bump: u8,
+    /// Role of the token delegate to create.
+    pub role: DelegateRole,
+    /// Authorization data forwarded to the Token Metadata `Delegate`.
+    pub auth_data: Option<AuthorizationData>,
+}
+
+/// Role of a token delegate created by `Delegate`, with its role-specific parameters.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum DelegateRole {
+    Transfer,
+    Sale,
+    Staking,
+    Utility,
+    /// Transfer delegate restricted to transfers to `locked_address`.
+    LockedTransfer {
+        locked_address: Pubkey,
+    },
 }
 
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub struct LockArgs {
     pub amount: u64,
-    pub bump: u8,
+    /// Authorization data forwarded to the Token Metadata `Delegate` and `Lock`.
+    pub auth_data: Option<AuthorizationData>,
 }
 
 #[repr(C)]
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub struct UnlockArgs {
+    /// Authorization data forwarded to the Token Metadata `Unlock`.
+    pub auth_data: Option<AuthorizationData>,
+}
+
+/// Legacy `Delegate` instruction data, which carried the Rooster PDA bump.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DeprecatedDelegateArgs {
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Legacy `Lock` instruction data, which carried the Rooster PDA bump.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DeprecatedLockArgs {
+    pub amount: u64,
+    pub bump: u8,
+}
+
+/// Legacy `Unlock` instruction data, which carried the Rooster PDA bump.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DeprecatedUnlockArgs {
     pub bump: u8,
 }
 
+impl From<DeprecatedDelegateArgs> for DelegateArgs {
+    fn from(args: DeprecatedDelegateArgs) -> Self {
+        Self {
+            amount: args.amount,
+            role: DelegateRole::Transfer,
+            auth_data: None,
+        }
+    }
+}
+
+impl From<DeprecatedLockArgs> for LockArgs {
+    fn from(args: DeprecatedLockArgs) -> Self {
+        Self {
+            amount: args.amount,
+            auth_data: None,
+        }
+    }
+}
+
+impl From<DeprecatedUnlockArgs> for UnlockArgs {
+    fn from(_args: DeprecatedUnlockArgs) -> Self {
+        Self { auth_data: None }
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CloseArgs {
+    /// Close the (empty) token accounts of the rooster PDA passed as remaining accounts.
+    pub close_token_accounts: bool,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct ProposeAuthorityArgs {
+    /// Authority to hand the rooster over to, `None` to cancel a pending proposal.
+    pub new_authority: Option<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetMultisigArgs {
+    /// Keys allowed to approve withdrawals and delegations.
+    pub signers: Vec<Pubkey>,
+    /// Number of approvals required, `0` (with no signers) to leave multisig mode.
+    pub threshold: u8,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetWithdrawDelayArgs {
+    /// Delay in seconds between queueing and executing a withdrawal, `0` to allow direct
+    /// withdrawals.
+    pub withdraw_delay: i64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetGuardianArgs {
+    /// Key allowed to pause the rooster, `None` to remove the guardian.
+    pub guardian: Option<Pubkey>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct SetPauseArgs {
+    /// Operations to pause, as a set of `PAUSE_*` flags (`0` resumes every operation).
+    pub pause_flags: u8,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct RevokeArgs {
+    /// Role of the token delegate to revoke.
+    pub role: TokenDelegateRole,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct BurnArgs {
+    pub amount: u64,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct CreateProgrammableArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    /// Creators of the asset, only the rooster PDA can be a verified creator.
+    pub creators: Option<Vec<Creator>>,
+    /// Print supply of the master edition, which must not be zero to print editions from it.
+    pub print_supply: PrintSupply,
+    /// Authorization data of the rule set, forwarded to the Mint CPI.
+    pub authorization_data: Option<AuthorizationData>,
+}
+
+/// Metadata update performed by the rooster PDA, depending on its role on the asset.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum UpdateMetadataArgs {
+    /// Update as the update authority of the asset.
+    AsUpdateAuthority {
+        data: Option<Data>,
+        /// Set, clear or leave the rule set of a programmable asset.
+        rule_set: RuleSetToggle,
+        auth_data: Option<AuthorizationData>,
+    },
+    /// Update as an AuthorityItem delegate of the asset.
+    AsAuthorityItemDelegate {
+        primary_sale_happened: Option<bool>,
+        is_mutable: Option<bool>,
+        auth_data: Option<AuthorizationData>,
+    },
+    /// Update as a ProgrammableConfig delegate of the asset.
+    AsProgrammableConfigDelegate {
+        /// Set, clear or leave the rule set of a programmable asset.
+        rule_set: RuleSetToggle,
+        auth_data: Option<AuthorizationData>,
+    },
+}
+
+/// Metadata-level delegate the rooster PDA can approve as the update authority of an asset.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MetadataDelegate {
+    Collection,
+    /// Token Metadata `AuthorityItem` delegate, formerly the `Update` delegate.
+    AuthorityItem,
+    ProgrammableConfig,
+}
+
+impl From<MetadataDelegate> for MetadataDelegateRole {
+    fn from(delegate: MetadataDelegate) -> Self {
+        match delegate {
+            MetadataDelegate::Collection => MetadataDelegateRole::Collection,
+            MetadataDelegate::AuthorityItem => MetadataDelegateRole::AuthorityItem,
+            MetadataDelegate::ProgrammableConfig => MetadataDelegateRole::ProgrammableConfig,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct DelegateMetadataArgs {
+    /// Role of the metadata delegate to create.
+    pub role: MetadataDelegate,
+    /// Authorization data forwarded to the Token Metadata `Delegate`.
+    pub auth_data: Option<AuthorizationData>,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct RevokeMetadataArgs {
+    /// Role of the metadata delegate to revoke.
+    pub role: MetadataDelegate,
+}
+
+/// What the rooster PDA verifies (or unverifies) on an asset.
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub enum VerificationArgs {
+    /// The rooster PDA as a creator of the asset.
+    Creator,
+    /// The collection of the asset, with the rooster PDA as the collection update authority.
+    Collection,
+}
+
+#[repr(C)]
+#[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
+pub struct PrintEditionArgs {
+    /// Edition number of the print.
+    pub edition: u64,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, PartialEq, Eq, Debug, Clone)]
 pub struct DelegateTransferArgs {
     pub amount: u64,
@@ -43,50 +288,177 @@ pub struct DelegateTransferArgs {
 #[derive(Debug, Clone, ShankInstruction, BorshSerialize, BorshDeserialize)]
 #[rustfmt::skip]
 pub enum RoosterCommand {
-    /// Initialize a new rooster
+    /// Initialize a new (unindexed) rooster
     #[account(0, writable, signer, name="authority", desc="Account owner")]
     #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
     #[account(2, name="system_program", desc = "The system program")]
     Init,
 
     /// Withdraw the token from the rooster by CPIing into Token Metadata 'Transfer'
+    /// (multisig signers are passed as remaining accounts)
     #[account(0, writable, signer, name="authority", desc="Account owner")]
     #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
     #[account(2, writable, name="token", desc = "Token account for rooster PDA")]
     #[account(3, name="destination_owner", desc = "Owner of the destination token account")]
     #[account(4, writable, name="destination", desc = "Destination token account")]
-    #[account(5, name="mint", desc = "Token mint")]
+    #[account(5, writable, name="mint", desc = "Token mint")]
     #[account(6, writable, name="metadata", desc = "Token metadata account")]
-    #[account(7, name="edition", desc = "Token edition account")]
-    #[account(8, name="token_record", desc = "Token record account")]
+    #[account(7, writable, name="edition", desc = "Token edition account")]
+    #[account(8, writable, name="owner_token_record", desc = "Owner token record account")]
+    #[account(9, writable, name="destination_token_record", desc = "Destination token record account")]
+    #[account(10, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(11, name="system_program", desc = "The system program")]
+    #[account(12, name="sysvar_instructions", desc = "The sysvar instructions")]
+    #[account(13, name="spl_token_program", desc = "The token program")]
+    #[account(14, name="spl_ata_program", desc = "The spl ata program")]
+    #[account(15, name="authorization_rules_program", desc = "The authorization rules program")]
+    #[account(16, name="authorization_rules", desc = "The authorization rules PDA account (optional)")]
+    Withdraw(WithdrawArgs),
+
+    /// Deprecated: use `Delegate`, which derives the Rooster PDA bump on-chain. Only the
+    /// instruction data is unchanged: the accounts are the `Delegate` ones, where the rooster
+    /// authority signs first instead of the delegate, so old clients must migrate.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="delegate", desc="Delegate account")]
+    #[account(2, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(3, writable, name="token", desc = "Token account for rooster PDA")]
+    #[account(4, name="mint", desc = "Token mint")]
+    #[account(5, writable, name="metadata", desc = "Token metadata account")]
+    #[account(6, name="edition", desc = "Token edition account")]
+    #[account(7, writable, name="token_record", desc = "Token record account")]
+    #[account(8, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(9, name="system_program", desc = "The system program")]
+    #[account(10, name="sysvar_instructions", desc = "The sysvar instructions")]
+    #[account(11, name="spl_token_program", desc = "The token program")]
+    #[account(12, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    DeprecatedDelegate(DeprecatedDelegateArgs),
+    
+    /// Deprecated: use `Lock`, which derives the Rooster PDA bump on-chain. Only the
+    /// instruction data is unchanged: the token owner must now be writable, as in `Lock`.
+    #[account(0, name="delegate", desc="Delegate PDA")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, writable, name="metadata", desc="Metadata account")]
+    #[account(5, name="edition", desc="Edition account")]
+    #[account(6, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="sysvar_instructions", desc="System program")]
+    #[account(9, name="spl_token_program", desc="SPL Token Program")]
+    DeprecatedLock(DeprecatedLockArgs),
+
+    /// Deprecated: use `Unlock`, which derives the Rooster PDA bump on-chain. Only the
+    /// instruction data is unchanged: the token owner must now be writable, as in `Unlock`.
+    #[account(0, name="delegate", desc="Delegate PDA")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, writable, name="metadata", desc="Metadata account")]
+    #[account(5, name="edition", desc="Edition account")]
+    #[account(6, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="sysvar_instructions", desc="System program")]
+    #[account(9, name="spl_token_program", desc="SPL Token Program")]
+    DeprecatedUnlock(DeprecatedUnlockArgs),
+
+    /// Deprecated: use `ProgrammableLock`, which derives the Rooster PDA bump on-chain. Only the
+    /// instruction data is unchanged: the token owner must now be writable, as in `ProgrammableLock`.
+    #[account(0, name="delegate", desc="Delegate PDA")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, writable, name="metadata", desc="Metadata account")]
+    #[account(5, name="edition", desc="Edition account")]
+    #[account(6, writable, name="token_record", desc="Token record account")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="System program")]
+    #[account(10, name="spl_token_program", desc="SPL Token Program")]
+    #[account(11, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(12, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    DeprecatedProgrammableLock(DeprecatedLockArgs),
+
+    /// Deprecated: use `ProgrammableUnlock`, which derives the Rooster PDA bump on-chain. Only the
+    /// instruction data is unchanged: the token owner must now be writable, as in `ProgrammableUnlock`.
+    #[account(0, name="delegate", desc="Delegate PDA")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
+    #[account(2, writable, name="token", desc="Token account")]
+    #[account(3, name="mint", desc="Mint account")]
+    #[account(4, writable, name="metadata", desc="Metadata account")]
+    #[account(5, name="edition", desc="Edition account")]
+    #[account(6, writable, name="token_record", desc="Token record account")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="System program")]
+    #[account(10, name="spl_token_program", desc="SPL Token Program")]
+    #[account(11, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(12, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    DeprecatedProgrammableUnlock(DeprecatedUnlockArgs),
+
+    /// Delegate transfer (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account as a delegate")]
+    #[account(2, writable, name="source_owner", desc = "Owner of the source token account")]
+    #[account(3, writable, name="source_token", desc = "Source token account")]
+    #[account(4, name="destination_owner", desc = "Owner of the destination token account")]
+    #[account(5, writable, name="destination_token", desc = "Destination token account")]
+    #[account(6, writable, name="mint", desc = "Token mint")]
+    #[account(7, writable, name="metadata", desc = "Token metadata account")]
+    #[account(8, writable, name="edition", desc = "Token edition account")]
+    #[account(9, writable, name="source_token_record", desc = "Source Token record account")]
+    #[account(10, writable, name="destination_token_record", desc = "Destination Token record account")]
+    #[account(11, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(12, name="system_program", desc = "The system program")]
+    #[account(13, name="sysvar_instructions", desc = "The sysvar instructions")]
+    #[account(14, name="spl_token_program", desc = "The token program")]
+    #[account(15, name="spl_ata_program", desc = "The spl ata program")]
+    #[account(16, name="authorization_rules_program", desc = "The authorization rules program")]
+    #[account(17, name="authorization_rules", desc = "The authorization rules PDA account (optional)")]
+    DelegateTransfer(DelegateTransferArgs),
+
+    /// Deposit the token into the rooster by CPIing into Token Metadata 'Transfer'
+    #[account(0, writable, signer, name="authority", desc="Account owner and token owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="token", desc = "Token account for the authority")]
+    #[account(3, writable, name="destination", desc = "Destination token account for rooster PDA")]
+    #[account(4, name="mint", desc = "Token mint")]
+    #[account(5, writable, name="metadata", desc = "Token metadata account")]
+    #[account(6, name="edition", desc = "Token edition account")]
+    #[account(7, writable, name="owner_token_record", desc = "Owner token record account")]
+    #[account(8, writable, name="destination_token_record", desc = "Destination token record account")]
     #[account(9, name="token_metadata_program", desc = "The token metadata program")]
     #[account(10, name="system_program", desc = "The system program")]
     #[account(11, name="sysvar_instructions", desc = "The sysvar instructions")]
     #[account(12, name="spl_token_program", desc = "The token program")]
     #[account(13, name="spl_ata_program", desc = "The spl ata program")]
     #[account(14, name="authorization_rules_program", desc = "The authorization rules program")]
-    #[account(15, name="authorization_rules", desc = "The authorization rules PDA account")]
-    Withdraw(WithdrawArgs),
+    #[account(15, name="authorization_rules", desc = "The authorization rules PDA account (optional)")]
+    Deposit(DepositArgs),
 
-    /// Create delegate via Token Metadata CPI
-    #[account(0, writable, signer, name="delegate", desc="Delegate account")]
-    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
-    #[account(2, writable, name="token", desc = "Token account for rooster PDA")]
-    #[account(3, name="mint", desc = "Token mint")]
-    #[account(4, writable, name="metadata", desc = "Token metadata account")]
-    #[account(5, name="edition", desc = "Token edition account")]
-    #[account(6, name="delegate_record", desc = "Collection delegate record account")]
-    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
-    #[account(8, name="system_program", desc = "The system program")]
-    #[account(9, name="sysvar_instructions", desc = "The sysvar instructions")]
-    #[account(10, name="spl_token_program", desc = "The token program")]
-    #[account(11, name="authorization_rules_program", desc="Token Authorization Rules Program")]
-    #[account(12, name="authorization_rules", desc="Token Authorization Rules account")]
+    /// Create a token delegate of the given role via Token Metadata CPI. Timelocked roosters
+    /// only approve Staking delegates, the other roles could bypass the withdraw queue.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="delegate", desc="Delegate account")]
+    #[account(2, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(3, writable, name="token", desc = "Token account for rooster PDA")]
+    #[account(4, name="mint", desc = "Token mint")]
+    #[account(5, writable, name="metadata", desc = "Token metadata account")]
+    #[account(6, name="edition", desc = "Token edition account")]
+    #[account(7, writable, name="token_record", desc = "Token record account")]
+    #[account(8, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(9, name="system_program", desc = "The system program")]
+    #[account(10, name="sysvar_instructions", desc = "The sysvar instructions")]
+    #[account(11, name="spl_token_program", desc = "The token program")]
+    #[account(12, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
     Delegate(DelegateArgs),
-    
+
     /// Locks a (non-programmable) token inplace via Token Metadata CPI
     #[account(0, name="delegate", desc="Delegate PDA")]
-    #[account(1, signer, name="token_owner", desc="Token owner")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
     #[account(2, writable, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
@@ -99,7 +471,7 @@ pub enum RoosterCommand {
 
     /// Unlocks a (non-programmable) token inplace via Token Metadata CPI
     #[account(0, name="delegate", desc="Delegate PDA")]
-    #[account(1, signer, name="token_owner", desc="Token owner")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
     #[account(2, writable, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
@@ -112,7 +484,7 @@ pub enum RoosterCommand {
 
     /// Locks a (non-programmable) token inplace via Token Metadata CPI
     #[account(0, name="delegate", desc="Delegate PDA")]
-    #[account(1, signer, name="token_owner", desc="Token owner")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
     #[account(2, writable, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
@@ -123,12 +495,12 @@ pub enum RoosterCommand {
     #[account(9, name="sysvar_instructions", desc="System program")]
     #[account(10, name="spl_token_program", desc="SPL Token Program")]
     #[account(11, name="authorization_rules_program", desc="Token Authorization Rules Program")]
-    #[account(12, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(12, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
     ProgrammableLock(LockArgs),
 
     /// Unlocks a (non-programmable) token inplace via Token Metadata CPI
     #[account(0, name="delegate", desc="Delegate PDA")]
-    #[account(1, signer, name="token_owner", desc="Token owner")]
+    #[account(1, writable, signer, name="token_owner", desc="Token owner")]
     #[account(2, writable, name="token", desc="Token account")]
     #[account(3, name="mint", desc="Mint account")]
     #[account(4, writable, name="metadata", desc="Metadata account")]
@@ -139,40 +511,281 @@ pub enum RoosterCommand {
     #[account(9, name="sysvar_instructions", desc="System program")]
     #[account(10, name="spl_token_program", desc="SPL Token Program")]
     #[account(11, name="authorization_rules_program", desc="Token Authorization Rules Program")]
-    #[account(12, name="authorization_rules", desc="Token Authorization Rules account")]
+    #[account(12, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
     ProgrammableUnlock(UnlockArgs),
 
-    /// Delegate transfer
+    /// Migrate a rooster from an older account layout to the current one
     #[account(0, writable, signer, name="authority", desc="Account owner")]
-    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account as a delegate")]
-    #[account(2, writable, name="source_owner", desc = "Owner of the source token account")]
-    #[account(3, writable, name="source_token", desc = "Source token account")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="system_program", desc = "The system program")]
+    MigrateRooster,
+
+    /// Close the rooster and send its lamports to the recipient. Rejected while withdrawals
+    /// are queued. The token accounts of the rooster PDA passed as remaining (writable)
+    /// accounts must be empty; the program cannot list the accounts the PDA owns, so this
+    /// check is best-effort and only covers the accounts passed.
+    /// (multisig signers are passed as remaining accounts, after the token accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="recipient", desc = "Recipient of the rooster lamports")]
+    #[account(3, name="spl_token_program", desc = "The token program")]
+    Close(CloseArgs),
+
+    /// Propose a new authority for the rooster, which takes over once it accepts
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    ProposeAuthority(ProposeAuthorityArgs),
+
+    /// Accept the authority of the rooster proposed by the current authority
+    #[account(0, signer, name="new_authority", desc="Proposed account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    AcceptAuthority,
+
+    /// Set the multisig signers and threshold of the rooster
+    /// (current multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    SetMultisig(SetMultisigArgs),
+
+    /// Set the delay between queueing and executing withdrawals of the rooster
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    SetWithdrawDelay(SetWithdrawDelayArgs),
+
+    /// Queue the withdrawal of a token from a timelocked rooster
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="withdraw_request", desc = "Withdraw request PDA account")]
+    #[account(3, name="mint", desc = "Token mint")]
     #[account(4, name="destination_owner", desc = "Owner of the destination token account")]
-    #[account(5, writable, name="destination_token", desc = "Destination token account")]
-    #[account(6, name="mint", desc = "Token mint")]
+    #[account(5, name="destination", desc = "Destination token account")]
+    #[account(6, name="system_program", desc = "The system program")]
+    QueueWithdraw(WithdrawArgs),
+
+    /// Execute a queued withdrawal once its delay has passed by CPIing into Token Metadata 'Transfer'
+    #[account(0, writable, name="withdraw_request", desc = "Withdraw request PDA account")]
+    #[account(1, writable, signer, name="authority", desc="Account owner")]
+    #[account(2, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(3, writable, name="token", desc = "Token account for rooster PDA")]
+    #[account(4, name="destination_owner", desc = "Owner of the destination token account")]
+    #[account(5, writable, name="destination", desc = "Destination token account")]
+    #[account(6, writable, name="mint", desc = "Token mint")]
     #[account(7, writable, name="metadata", desc = "Token metadata account")]
-    #[account(8, name="edition", desc = "Token edition account")]
-    #[account(9, name="source_token_record", desc = "Source Token record account")]
-    #[account(10, name="dest_token_record", desc = "Destination Token record account")]
+    #[account(8, writable, name="edition", desc = "Token edition account")]
+    #[account(9, writable, name="owner_token_record", desc = "Owner token record account")]
+    #[account(10, writable, name="destination_token_record", desc = "Destination token record account")]
     #[account(11, name="token_metadata_program", desc = "The token metadata program")]
     #[account(12, name="system_program", desc = "The system program")]
     #[account(13, name="sysvar_instructions", desc = "The sysvar instructions")]
     #[account(14, name="spl_token_program", desc = "The token program")]
     #[account(15, name="spl_ata_program", desc = "The spl ata program")]
     #[account(16, name="authorization_rules_program", desc = "The authorization rules program")]
-    #[account(17, name="authorization_rules", desc = "The authorization rules PDA account")]
-    DelegateTransfer(DelegateTransferArgs),
+    #[account(17, name="authorization_rules", desc = "The authorization rules PDA account (optional)")]
+    ExecuteWithdraw(WithdrawArgs),
+
+    /// Cancel a queued withdrawal and send the request lamports back to the authority
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="withdraw_request", desc = "Withdraw request PDA account")]
+    CancelWithdraw,
+
+    /// Set the guardian allowed to pause the rooster
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    SetGuardian(SetGuardianArgs),
+
+    /// Set the pause flags of the rooster (the guardian can only add flags)
+    #[account(0, signer, name="authority", desc="Account owner or guardian")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    SetPause(SetPauseArgs),
+
+    /// Revoke a token delegate via Token Metadata CPI. The token must be owned by either the
+    /// rooster PDA or the rooster authority, which signs the revoke accordingly.
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="delegate", desc="Delegate account")]
+    #[account(3, writable, name="token", desc="Token account")]
+    #[account(4, name="mint", desc="Mint account")]
+    #[account(5, writable, name="metadata", desc="Metadata account")]
+    #[account(6, name="edition", desc="Edition account")]
+    #[account(7, writable, name="token_record", desc="Token record account (Token Metadata program for legacy tokens)")]
+    #[account(8, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(9, name="system_program", desc="System program")]
+    #[account(10, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(11, name="spl_token_program", desc="SPL Token Program")]
+    #[account(12, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    Revoke(RevokeArgs),
+
+    /// Burn a token owned by or delegated (Utility) to the rooster PDA via Token Metadata CPI.
+    /// Optional accounts are replaced by the Token Metadata program when absent, and the rent
+    /// of the closed accounts goes to the rooster PDA. Rejected on timelocked roosters, since
+    /// burns cannot be queued like withdrawals.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="collection_metadata", desc="Collection metadata account (optional)")]
+    #[account(3, writable, name="metadata", desc="Metadata account")]
+    #[account(4, writable, name="edition", desc="Master or print edition account")]
+    #[account(5, writable, name="mint", desc="Mint account")]
+    #[account(6, writable, name="token", desc="Token account")]
+    #[account(7, writable, name="master_edition", desc="Master edition of a print edition (optional)")]
+    #[account(8, name="master_edition_mint", desc="Master edition mint of a print edition (optional)")]
+    #[account(9, name="master_edition_token", desc="Master edition token account of a print edition (optional)")]
+    #[account(10, writable, name="edition_marker", desc="Edition marker of a print edition (optional)")]
+    #[account(11, writable, name="token_record", desc="Token record account (optional)")]
+    #[account(12, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(13, name="system_program", desc="System program")]
+    #[account(14, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(15, name="spl_token_program", desc="SPL Token Program")]
+    Burn(BurnArgs),
+
+    /// Create and mint a pNFT into the rooster PDA via Token Metadata CPIs. The rooster PDA
+    /// is the mint authority, update authority and owner of the asset, while the authority
+    /// pays for the new accounts (the rooster PDA holds data, so it cannot fund accounts).
+    /// The rule set of the asset is the authorization rules account, if any.
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, signer, name="mint", desc="Uninitialized mint account")]
+    #[account(3, writable, name="metadata", desc="Metadata account")]
+    #[account(4, writable, name="edition", desc="Master edition account")]
+    #[account(5, writable, name="token", desc="Associated token account of the rooster PDA")]
+    #[account(6, writable, name="token_record", desc="Token record account")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(10, name="spl_token_program", desc="SPL Token Program")]
+    #[account(11, name="spl_ata_program", desc = "The spl ata program")]
+    #[account(12, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(13, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    CreateProgrammable(CreateProgrammableArgs),
+
+    /// Update the metadata of an asset via Token Metadata CPI, signed by the rooster PDA as the
+    /// update authority or a metadata delegate (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="delegate_record", desc="Metadata delegate record (optional)")]
+    #[account(3, name="token", desc="Token account (optional)")]
+    #[account(4, name="mint", desc="Mint account")]
+    #[account(5, writable, name="metadata", desc="Metadata account")]
+    #[account(6, name="edition", desc="Edition account (optional)")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(10, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(11, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    UpdateMetadata(UpdateMetadataArgs),
+
+    /// Create a metadata delegate of an asset whose update authority is the rooster PDA via
+    /// Token Metadata CPI (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="delegate", desc="Delegate account")]
+    #[account(3, writable, name="delegate_record", desc="Metadata delegate record account")]
+    #[account(4, name="mint", desc="Mint account")]
+    #[account(5, writable, name="metadata", desc="Metadata account")]
+    #[account(6, name="edition", desc="Edition account")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(10, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(11, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    DelegateMetadata(DelegateMetadataArgs),
+
+    /// Revoke a metadata delegate of an asset whose update authority is the rooster PDA via
+    /// Token Metadata CPI
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="delegate", desc="Delegate account")]
+    #[account(3, writable, name="delegate_record", desc="Metadata delegate record account")]
+    #[account(4, name="mint", desc="Mint account")]
+    #[account(5, writable, name="metadata", desc="Metadata account")]
+    #[account(6, name="edition", desc="Edition account")]
+    #[account(7, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(8, name="system_program", desc="System program")]
+    #[account(9, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(10, name="authorization_rules_program", desc="Token Authorization Rules Program")]
+    #[account(11, name="authorization_rules", desc="Token Authorization Rules account (optional)")]
+    RevokeMetadata(RevokeMetadataArgs),
+
+    /// Verify the rooster PDA as a creator, or the collection of an asset, via Token Metadata
+    /// CPI. Collection accounts are replaced by the Token Metadata program for creators.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, name="collection_mint", desc="Collection mint (optional)")]
+    #[account(4, writable, name="collection_metadata", desc="Collection metadata account (optional)")]
+    #[account(5, name="collection_master_edition", desc="Collection master edition account (optional)")]
+    #[account(6, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="sysvar_instructions", desc="Sysvar instructions account")]
+    Verify(VerificationArgs),
+
+    /// Unverify the rooster PDA as a creator, or the collection of an asset, via Token Metadata
+    /// CPI. Collection accounts are replaced by the Token Metadata program for creators.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, signer, name="authority", desc="Account owner")]
+    #[account(1, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, name="metadata", desc="Metadata account")]
+    #[account(3, name="collection_mint", desc="Collection mint (optional)")]
+    #[account(4, writable, name="collection_metadata", desc="Collection metadata account (optional)")]
+    #[account(5, name="collection_master_edition", desc="Collection master edition account (optional)")]
+    #[account(6, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(7, name="system_program", desc="System program")]
+    #[account(8, name="sysvar_instructions", desc="Sysvar instructions account")]
+    Unverify(VerificationArgs),
+
+    /// Print an edition from a master edition held by the rooster PDA via Token Metadata CPI.
+    /// The authority pays for the print and is the mint authority of the new mint.
+    /// (multisig signers are passed as remaining accounts)
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, writable, signer, name="edition_mint", desc="Uninitialized mint of the print")]
+    #[account(3, writable, name="edition_metadata", desc="Metadata account of the print")]
+    #[account(4, writable, name="edition", desc="Edition account of the print")]
+    #[account(5, name="edition_token_owner", desc="Owner of the print token, usually the rooster PDA")]
+    #[account(6, writable, name="edition_token", desc="Associated token account of the print")]
+    #[account(7, writable, name="edition_token_record", desc="Token record of the print (optional)")]
+    #[account(8, writable, name="master_edition", desc="Master edition account")]
+    #[account(9, writable, name="edition_marker", desc="Edition marker account")]
+    #[account(10, name="master_token", desc="Token account of the rooster PDA holding the master edition")]
+    #[account(11, name="master_metadata", desc="Metadata account of the master edition")]
+    #[account(12, name="update_authority", desc="Update authority of the master edition")]
+    #[account(13, name="token_metadata_program", desc = "The token metadata program")]
+    #[account(14, name="spl_token_program", desc="SPL Token Program")]
+    #[account(15, name="spl_ata_program", desc = "The spl ata program")]
+    #[account(16, name="sysvar_instructions", desc="Sysvar instructions account")]
+    #[account(17, name="system_program", desc="System program")]
+    PrintEdition(PrintEditionArgs),
+
+    /// Initialize a new rooster at the given vault index. `Init` keeps its original layout
+    /// and initializes the unindexed rooster.
+    #[account(0, writable, signer, name="authority", desc="Account owner")]
+    #[account(1, writable, name="rooster_pda", desc = "Rooster PDA account")]
+    #[account(2, name="system_program", desc = "The system program")]
+    InitIndexed(InitArgs),
 }
 
-pub fn init(authority: Pubkey, rooster_pda: Pubkey) -> Instruction {
+/// Builds an `InitIndexed` instruction, or a legacy `Init` one for the unindexed rooster.
+pub fn init(authority: Pubkey, rooster_pda: Pubkey, args: InitArgs) -> Instruction {
+    let command = match args.index {
+        Some(_) => RoosterCommand::InitIndexed(args),
+        None => RoosterCommand::Init,
+    };
+
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, true),
-            AccountMeta::new(rooster_pda, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-        ],
-        data: RoosterCommand::Init.try_to_vec().unwrap(),
+        accounts: InitKeys {
+            authority,
+            rooster_pda,
+            system_program: solana_program::system_program::id(),
+        }
+        .to_account_metas(),
+        data: command.try_to_vec().unwrap(),
     }
 }
 
@@ -186,7 +799,7 @@ pub fn withdraw(
     mint: Pubkey,
     metadata: Pubkey,
     edition: Pubkey,
-    rule_set: Pubkey,
+    authorization_rules: Option<Pubkey>,
     spl_token_program: Pubkey,
     args: WithdrawArgs,
 ) -> Instruction {
@@ -195,31 +808,33 @@ pub fn withdraw(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, true),
-            AccountMeta::new(rooster_pda, false),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(destination_owner, false),
-            AccountMeta::new(destination, false),
-            AccountMeta::new(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new(edition, false),
-            AccountMeta::new(owner_token_record, false),
-            AccountMeta::new(destination_token_record, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(SPL_ATA_TOKEN_PROGRAM_ID, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(rule_set, false),
-        ],
+        accounts: WithdrawKeys {
+            authority,
+            rooster_pda,
+            token,
+            destination_owner,
+            destination,
+            mint,
+            metadata,
+            edition,
+            owner_token_record,
+            destination_token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            spl_ata_program: SPL_ATA_TOKEN_PROGRAM_ID,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
         data: RoosterCommand::Withdraw(args).try_to_vec().unwrap(),
     }
 }
 
 #[allow(clippy::too_many_arguments)]
 pub fn delegate(
+    authority: Pubkey,
     delegate: Pubkey,
     rooster_pda: Pubkey,
     token: Pubkey,
@@ -234,24 +849,23 @@ pub fn delegate(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(delegate, true),
-            AccountMeta::new(rooster_pda, false),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new_readonly(edition, false),
-            AccountMeta::new(token_record, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(
-                authorization_rules.ok_or(mpl_token_metadata::ID).unwrap(),
-                false,
-            ),
-        ],
+        accounts: DelegateKeys {
+            authority,
+            delegate,
+            rooster_pda,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
         data: RoosterCommand::Delegate(args).try_to_vec().unwrap(),
     }
 }
@@ -269,20 +883,19 @@ pub fn lock(
 ) -> Instruction {
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, false),
-            AccountMeta::new(token_owner, true),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new_readonly(edition, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-        ],
+        accounts: LockKeys {
+            delegate: authority,
+            token_owner,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+        }
+        .to_account_metas(),
         data: RoosterCommand::Lock(args).try_to_vec().unwrap(),
     }
 }
@@ -300,20 +913,19 @@ pub fn unlock(
 ) -> Instruction {
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, false),
-            AccountMeta::new(token_owner, true),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new_readonly(edition, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-        ],
+        accounts: LockKeys {
+            delegate: authority,
+            token_owner,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+        }
+        .to_account_metas(),
         data: RoosterCommand::Unlock(args).try_to_vec().unwrap(),
     }
 }
@@ -334,24 +946,22 @@ pub fn programmable_lock(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, false),
-            AccountMeta::new(token_owner, true),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new_readonly(edition, false),
-            AccountMeta::new(token_record, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(
-                authorization_rules.ok_or(mpl_token_metadata::ID).unwrap(),
-                false,
-            ),
-        ],
+        accounts: ProgrammableLockKeys {
+            delegate: authority,
+            token_owner,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
         data: RoosterCommand::ProgrammableLock(args).try_to_vec().unwrap(),
     }
 }
@@ -372,24 +982,22 @@ pub fn programmable_unlock(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, false),
-            AccountMeta::new(token_owner, true),
-            AccountMeta::new(token, false),
-            AccountMeta::new_readonly(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new_readonly(edition, false),
-            AccountMeta::new(token_record, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(
-                authorization_rules.ok_or(mpl_token_metadata::ID).unwrap(),
-                false,
-            ),
-        ],
+        accounts: ProgrammableLockKeys {
+            delegate: authority,
+            token_owner,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
         data: RoosterCommand::ProgrammableUnlock(args)
             .try_to_vec()
             .unwrap(),
@@ -405,7 +1013,7 @@ pub fn delegate_transfer(
     destination_owner: Pubkey,
     destination_token: Pubkey,
     mint: Pubkey,
-    rule_set: Pubkey,
+    authorization_rules: Option<Pubkey>,
     spl_token_program: Pubkey,
     args: DelegateTransferArgs,
 ) -> Instruction {
@@ -416,26 +1024,645 @@ pub fn delegate_transfer(
 
     Instruction {
         program_id: crate::ID,
-        accounts: vec![
-            AccountMeta::new(authority, true),
-            AccountMeta::new(rooster_pda, false),
-            AccountMeta::new(source_owner, false),
-            AccountMeta::new(source_token, false),
-            AccountMeta::new_readonly(destination_owner, false),
-            AccountMeta::new(destination_token, false),
-            AccountMeta::new(mint, false),
-            AccountMeta::new(metadata, false),
-            AccountMeta::new(edition, false),
-            AccountMeta::new(source_token_record, false),
-            AccountMeta::new(destination_token_record, false),
-            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
-            AccountMeta::new_readonly(solana_program::system_program::id(), false),
-            AccountMeta::new_readonly(solana_program::sysvar::instructions::id(), false),
-            AccountMeta::new_readonly(spl_token_program, false),
-            AccountMeta::new_readonly(SPL_ATA_TOKEN_PROGRAM_ID, false),
-            AccountMeta::new_readonly(MPL_TOKEN_AUTH_RULES_PROGRAM_ID, false),
-            AccountMeta::new_readonly(rule_set, false),
-        ],
+        accounts: DelegateTransferKeys {
+            authority,
+            rooster_pda,
+            source_owner,
+            source_token,
+            destination_owner,
+            destination_token,
+            mint,
+            metadata,
+            edition,
+            source_token_record,
+            destination_token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            spl_ata_program: SPL_ATA_TOKEN_PROGRAM_ID,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
         data: RoosterCommand::DelegateTransfer(args).try_to_vec().unwrap(),
     }
 }
+
+#[allow(clippy::too_many_arguments)]
+pub fn deposit(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    token: Pubkey,
+    destination: Pubkey,
+    mint: Pubkey,
+    metadata: Pubkey,
+    edition: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    spl_token_program: Pubkey,
+    args: DepositArgs,
+) -> Instruction {
+    let (owner_token_record, _) = find_token_record_account(&mint, &token);
+    let (destination_token_record, _) = find_token_record_account(&mint, &destination);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: DepositKeys {
+            authority,
+            rooster_pda,
+            token,
+            destination,
+            mint,
+            metadata,
+            edition,
+            owner_token_record,
+            destination_token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            spl_ata_program: SPL_ATA_TOKEN_PROGRAM_ID,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::Deposit(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn migrate_rooster(authority: Pubkey, rooster_pda: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: MigrateRoosterKeys {
+            authority,
+            rooster_pda,
+            system_program: solana_program::system_program::id(),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::MigrateRooster.try_to_vec().unwrap(),
+    }
+}
+
+pub fn close(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    recipient: Pubkey,
+    token_accounts: &[Pubkey],
+    args: CloseArgs,
+) -> Instruction {
+    let mut accounts = CloseKeys {
+        authority,
+        rooster_pda,
+        recipient,
+        spl_token_program: spl_token::ID,
+    }
+    .to_account_metas();
+    accounts.extend(
+        token_accounts
+            .iter()
+            .map(|token_account| AccountMeta::new(*token_account, false)),
+    );
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: RoosterCommand::Close(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn propose_authority(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    args: ProposeAuthorityArgs,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: RoosterAuthorityKeys {
+            authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::ProposeAuthority(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn accept_authority(new_authority: Pubkey, rooster_pda: Pubkey) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: AcceptAuthorityKeys {
+            new_authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::AcceptAuthority.try_to_vec().unwrap(),
+    }
+}
+
+pub fn set_multisig(authority: Pubkey, rooster_pda: Pubkey, args: SetMultisigArgs) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: RoosterAuthorityKeys {
+            authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::SetMultisig(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn set_withdraw_delay(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    args: SetWithdrawDelayArgs,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: RoosterAuthorityKeys {
+            authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::SetWithdrawDelay(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn queue_withdraw(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    mint: Pubkey,
+    destination_owner: Pubkey,
+    destination: Pubkey,
+    args: WithdrawArgs,
+) -> Instruction {
+    let (withdraw_request, _) = find_withdraw_request_pda(&rooster_pda, &mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: QueueWithdrawKeys {
+            authority,
+            rooster_pda,
+            withdraw_request,
+            mint,
+            destination_owner,
+            destination,
+            system_program: solana_program::system_program::id(),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::QueueWithdraw(args).try_to_vec().unwrap(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_withdraw(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    token: Pubkey,
+    destination_owner: Pubkey,
+    destination: Pubkey,
+    mint: Pubkey,
+    metadata: Pubkey,
+    edition: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    spl_token_program: Pubkey,
+    args: WithdrawArgs,
+) -> Instruction {
+    let (withdraw_request, _) = find_withdraw_request_pda(&rooster_pda, &mint);
+
+    // the accounts of a direct withdrawal, preceded by the withdraw request
+    let mut instruction = withdraw(
+        authority,
+        rooster_pda,
+        token,
+        destination_owner,
+        destination,
+        mint,
+        metadata,
+        edition,
+        authorization_rules,
+        spl_token_program,
+        args.clone(),
+    );
+    instruction
+        .accounts
+        .insert(0, AccountMeta::new(withdraw_request, false));
+    instruction.data = RoosterCommand::ExecuteWithdraw(args).try_to_vec().unwrap();
+    instruction
+}
+
+pub fn cancel_withdraw(authority: Pubkey, rooster_pda: Pubkey, mint: Pubkey) -> Instruction {
+    let (withdraw_request, _) = find_withdraw_request_pda(&rooster_pda, &mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: CancelWithdrawKeys {
+            authority,
+            rooster_pda,
+            withdraw_request,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::CancelWithdraw.try_to_vec().unwrap(),
+    }
+}
+
+pub fn set_guardian(authority: Pubkey, rooster_pda: Pubkey, args: SetGuardianArgs) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: RoosterAuthorityKeys {
+            authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::SetGuardian(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Builds a `SetPause` instruction signed by either the rooster authority or its guardian.
+pub fn set_pause(authority: Pubkey, rooster_pda: Pubkey, args: SetPauseArgs) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: SetPauseKeys {
+            authority,
+            rooster_pda,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::SetPause(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Builds a `Revoke` instruction. Legacy (non-programmable) tokens have no token record.
+#[allow(clippy::too_many_arguments)]
+pub fn revoke(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    delegate: Pubkey,
+    token: Pubkey,
+    mint: Pubkey,
+    metadata: Pubkey,
+    edition: Pubkey,
+    programmable: bool,
+    authorization_rules: Option<Pubkey>,
+    spl_token_program: Pubkey,
+    args: RevokeArgs,
+) -> Instruction {
+    let token_record = if programmable {
+        find_token_record_account(&mint, &token).0
+    } else {
+        mpl_token_metadata::ID
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: RevokeKeys {
+            authority,
+            rooster_pda,
+            delegate,
+            token,
+            mint,
+            metadata,
+            edition,
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::Revoke(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Builds a `Burn` instruction. The master edition accounts are only set when burning a print
+/// edition, and legacy (non-programmable) tokens have no token record.
+#[allow(clippy::too_many_arguments)]
+pub fn burn(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    token: Pubkey,
+    mint: Pubkey,
+    collection_metadata: Option<Pubkey>,
+    master_edition_mint: Option<Pubkey>,
+    master_edition_token: Option<Pubkey>,
+    edition_marker: Option<Pubkey>,
+    programmable: bool,
+    spl_token_program: Pubkey,
+    args: BurnArgs,
+) -> Instruction {
+    let (metadata, _) = find_metadata_account(&mint);
+    let (edition, _) = find_master_edition_account(&mint);
+    let master_edition = master_edition_mint.map(|mint| find_master_edition_account(&mint).0);
+    let token_record = if programmable {
+        find_token_record_account(&mint, &token).0
+    } else {
+        mpl_token_metadata::ID
+    };
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: BurnKeys {
+            authority,
+            rooster_pda,
+            collection_metadata: collection_metadata.unwrap_or(mpl_token_metadata::ID),
+            metadata,
+            edition,
+            mint,
+            token,
+            master_edition: master_edition.unwrap_or(mpl_token_metadata::ID),
+            master_edition_mint: master_edition_mint.unwrap_or(mpl_token_metadata::ID),
+            master_edition_token: master_edition_token.unwrap_or(mpl_token_metadata::ID),
+            edition_marker: edition_marker.unwrap_or(mpl_token_metadata::ID),
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+        }
+        .to_account_metas(),
+        data: RoosterCommand::Burn(args).try_to_vec().unwrap(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_programmable(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    mint: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    spl_token_program: Pubkey,
+    args: CreateProgrammableArgs,
+) -> Instruction {
+    let (metadata, _) = find_metadata_account(&mint);
+    let (edition, _) = find_master_edition_account(&mint);
+    let (token, _) = find_associated_token_account(&rooster_pda, &mint, &spl_token_program);
+    let (token_record, _) = find_token_record_account(&mint, &token);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: CreateProgrammableKeys {
+            authority,
+            rooster_pda,
+            mint,
+            metadata,
+            edition,
+            token,
+            token_record,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            spl_token_program,
+            spl_ata_program: SPL_ATA_TOKEN_PROGRAM_ID,
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::CreateProgrammable(args)
+            .try_to_vec()
+            .unwrap(),
+    }
+}
+
+/// Builds an `UpdateMetadata` instruction. The metadata delegate record of the rooster PDA is
+/// derived from the `update_authority` of the asset when updating as a delegate.
+#[allow(clippy::too_many_arguments)]
+pub fn update_metadata(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    update_authority: Pubkey,
+    mint: Pubkey,
+    token: Option<Pubkey>,
+    authorization_rules: Option<Pubkey>,
+    args: UpdateMetadataArgs,
+) -> Instruction {
+    let (metadata, _) = find_metadata_account(&mint);
+    let (edition, _) = find_master_edition_account(&mint);
+    let delegate_role = match args {
+        UpdateMetadataArgs::AsUpdateAuthority { .. } => None,
+        UpdateMetadataArgs::AsAuthorityItemDelegate { .. } => {
+            Some(MetadataDelegateRole::AuthorityItem)
+        }
+        UpdateMetadataArgs::AsProgrammableConfigDelegate { .. } => {
+            Some(MetadataDelegateRole::ProgrammableConfig)
+        }
+    };
+    let delegate_record = delegate_role.map(|role| {
+        find_metadata_delegate_record_account(&mint, role, &update_authority, &rooster_pda).0
+    });
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: UpdateMetadataKeys {
+            authority,
+            rooster_pda,
+            delegate_record: delegate_record.unwrap_or(mpl_token_metadata::ID),
+            token: token.unwrap_or(mpl_token_metadata::ID),
+            mint,
+            metadata,
+            edition,
+            token_metadata_program: mpl_token_metadata::ID,
+            system_program: solana_program::system_program::id(),
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+            authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::UpdateMetadata(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn delegate_metadata(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    delegate: Pubkey,
+    mint: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    args: DelegateMetadataArgs,
+) -> Instruction {
+    let accounts = metadata_delegate_accounts(
+        authority,
+        rooster_pda,
+        delegate,
+        mint,
+        authorization_rules,
+        args.role,
+    );
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: RoosterCommand::DelegateMetadata(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn revoke_metadata(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    delegate: Pubkey,
+    mint: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    args: RevokeMetadataArgs,
+) -> Instruction {
+    let accounts = metadata_delegate_accounts(
+        authority,
+        rooster_pda,
+        delegate,
+        mint,
+        authorization_rules,
+        args.role,
+    );
+
+    Instruction {
+        program_id: crate::ID,
+        accounts,
+        data: RoosterCommand::RevokeMetadata(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Accounts of `DelegateMetadata` and `RevokeMetadata`, with the delegate record derived for the
+/// rooster PDA as update authority.
+fn metadata_delegate_accounts(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    delegate: Pubkey,
+    mint: Pubkey,
+    authorization_rules: Option<Pubkey>,
+    role: MetadataDelegate,
+) -> Vec<AccountMeta> {
+    let (metadata, _) = find_metadata_account(&mint);
+    let (edition, _) = find_master_edition_account(&mint);
+    let (delegate_record, _) =
+        find_metadata_delegate_record_account(&mint, role.into(), &rooster_pda, &delegate);
+
+    MetadataDelegateKeys {
+        authority,
+        rooster_pda,
+        delegate,
+        delegate_record,
+        mint,
+        metadata,
+        edition,
+        token_metadata_program: mpl_token_metadata::ID,
+        system_program: solana_program::system_program::id(),
+        sysvar_instructions: solana_program::sysvar::instructions::id(),
+        authorization_rules_program: MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+        authorization_rules: authorization_rules.unwrap_or(mpl_token_metadata::ID),
+    }
+    .to_account_metas()
+}
+
+pub fn verify(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    mint: Pubkey,
+    collection_mint: Option<Pubkey>,
+    args: VerificationArgs,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: verification_accounts(authority, rooster_pda, mint, collection_mint),
+        data: RoosterCommand::Verify(args).try_to_vec().unwrap(),
+    }
+}
+
+pub fn unverify(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    mint: Pubkey,
+    collection_mint: Option<Pubkey>,
+    args: VerificationArgs,
+) -> Instruction {
+    Instruction {
+        program_id: crate::ID,
+        accounts: verification_accounts(authority, rooster_pda, mint, collection_mint),
+        data: RoosterCommand::Unverify(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Accounts of `Verify` and `Unverify`, with the collection accounts derived from the
+/// collection mint when verifying a collection.
+fn verification_accounts(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    mint: Pubkey,
+    collection_mint: Option<Pubkey>,
+) -> Vec<AccountMeta> {
+    let (metadata, _) = find_metadata_account(&mint);
+    let collection_metadata = collection_mint.map(|mint| find_metadata_account(&mint).0);
+    let collection_master_edition =
+        collection_mint.map(|mint| find_master_edition_account(&mint).0);
+
+    VerificationKeys {
+        authority,
+        rooster_pda,
+        metadata,
+        collection_mint: collection_mint.unwrap_or(mpl_token_metadata::ID),
+        collection_metadata: collection_metadata.unwrap_or(mpl_token_metadata::ID),
+        collection_master_edition: collection_master_edition.unwrap_or(mpl_token_metadata::ID),
+        token_metadata_program: mpl_token_metadata::ID,
+        system_program: solana_program::system_program::id(),
+        sysvar_instructions: solana_program::sysvar::instructions::id(),
+    }
+    .to_account_metas()
+}
+
+/// Builds a `PrintEdition` instruction. The print is minted to the rooster PDA unless an
+/// `edition_token_owner` is given.
+#[allow(clippy::too_many_arguments)]
+pub fn print_edition(
+    authority: Pubkey,
+    rooster_pda: Pubkey,
+    edition_mint: Pubkey,
+    edition_token_owner: Option<Pubkey>,
+    master_mint: Pubkey,
+    update_authority: Pubkey,
+    spl_token_program: Pubkey,
+    args: PrintEditionArgs,
+) -> Instruction {
+    let edition_token_owner = edition_token_owner.unwrap_or(rooster_pda);
+    let (edition_metadata, _) = find_metadata_account(&edition_mint);
+    let (edition, _) = find_master_edition_account(&edition_mint);
+    let (edition_token, _) =
+        find_associated_token_account(&edition_token_owner, &edition_mint, &spl_token_program);
+    let (edition_token_record, _) = find_token_record_account(&edition_mint, &edition_token);
+    let (master_edition, _) = find_master_edition_account(&master_mint);
+    let (edition_marker, _) = find_edition_marker_account(&master_mint, args.edition);
+    let (master_token, _) =
+        find_associated_token_account(&rooster_pda, &master_mint, &spl_token_program);
+    let (master_metadata, _) = find_metadata_account(&master_mint);
+
+    Instruction {
+        program_id: crate::ID,
+        accounts: PrintEditionKeys {
+            authority,
+            rooster_pda,
+            edition_mint,
+            edition_metadata,
+            edition,
+            edition_token_owner,
+            edition_token,
+            edition_token_record,
+            master_edition,
+            edition_marker,
+            master_token,
+            master_metadata,
+            update_authority,
+            token_metadata_program: mpl_token_metadata::ID,
+            spl_token_program,
+            spl_ata_program: SPL_ATA_TOKEN_PROGRAM_ID,
+            sysvar_instructions: solana_program::sysvar::instructions::id(),
+            system_program: solana_program::system_program::id(),
+        }
+        .to_account_metas(),
+        data: RoosterCommand::PrintEdition(args).try_to_vec().unwrap(),
+    }
+}
+
+/// Appends the multisig signers approving the instruction as (signer) remaining accounts.
+pub fn with_multisig_signers(mut instruction: Instruction, signers: &[Pubkey]) -> Instruction {
+    instruction.accounts.extend(
+        signers
+            .iter()
+            .map(|signer| AccountMeta::new_readonly(*signer, true)),
+    );
+    instruction
+}
@@ -1,5 +1,5 @@
 use crate::{error::Crows, instruction::RoosterCommand};
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshDeserialize;
 use mpl_token_metadata::instruction::{
     builders::TransferBuilder, InstructionBuilder, TransferArgs,
 };
@@ -9,12 +9,12 @@ use solana_program::{
     instruction::{AccountMeta, Instruction},
     msg,
     program_error::ProgramError,
-    program_memory::sol_memcpy,
     pubkey,
     pubkey::Pubkey,
 };
 
 pub mod assertions;
+pub mod context;
 #[cfg(not(feature = "no-entrypoint"))]
 pub mod entrypoint;
 pub mod error;
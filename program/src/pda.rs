@@ -1,6 +1,100 @@
+use std::ops::Range;
+
+use mpl_token_metadata::state::EDITION_MARKER_BIT_SIZE;
+
 use super::*;
 
+pub const ROOSTER_PREFIX: &[u8] = b"rooster";
+
+pub const WITHDRAW_REQUEST_PREFIX: &[u8] = b"withdraw_request";
+
 pub fn find_rooster_pda(authority: &Pubkey) -> (Pubkey, u8) {
-    let seeds = &[b"rooster", authority.as_ref()];
-    Pubkey::find_program_address(seeds, &crate::ID)
+    find_indexed_rooster_pda(authority, None)
+}
+
+/// Derives the rooster PDA for the vault `index` of the authority. The `None` index is the
+/// authority's original (unindexed) rooster. The authority in the seeds is the one that
+/// created the rooster, even after the rooster authority has been transferred.
+pub fn find_indexed_rooster_pda(authority: &Pubkey, index: Option<u64>) -> (Pubkey, u8) {
+    let seeds = RoosterSeeds::new(authority, index);
+    Pubkey::find_program_address(&seeds.seeds(), &crate::ID)
+}
+
+/// Enumerates the indexed rooster PDAs of the authority in the given index range.
+pub fn find_indexed_rooster_pdas(authority: &Pubkey, indices: Range<u64>) -> Vec<(u64, Pubkey)> {
+    indices
+        .map(|index| (index, find_indexed_rooster_pda(authority, Some(index)).0))
+        .collect()
+}
+
+/// Derives the PDA of the withdrawal of `mint` queued on the rooster.
+pub fn find_withdraw_request_pda(rooster: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[WITHDRAW_REQUEST_PREFIX, rooster.as_ref(), mint.as_ref()],
+        &crate::ID,
+    )
+}
+
+/// Derives the associated token account of `owner` for `mint`.
+pub fn find_associated_token_account(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    spl_token_program: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), spl_token_program.as_ref(), mint.as_ref()],
+        &SPL_ATA_TOKEN_PROGRAM_ID,
+    )
+}
+
+/// Derives the edition marker of the master edition of `master_mint` that tracks the print
+/// `edition`.
+pub fn find_edition_marker_account(master_mint: &Pubkey, edition: u64) -> (Pubkey, u8) {
+    let marker_number = (edition / EDITION_MARKER_BIT_SIZE).to_string();
+    Pubkey::find_program_address(
+        &[
+            mpl_token_metadata::pda::PREFIX.as_bytes(),
+            mpl_token_metadata::ID.as_ref(),
+            master_mint.as_ref(),
+            mpl_token_metadata::pda::EDITION.as_bytes(),
+            marker_number.as_bytes(),
+        ],
+        &mpl_token_metadata::ID,
+    )
+}
+
+/// Owned seeds of a rooster PDA, used to build both the derivation and the signer seeds.
+pub struct RoosterSeeds {
+    authority: Pubkey,
+    index: Option<[u8; 8]>,
+    bump: Option<[u8; 1]>,
+}
+
+impl RoosterSeeds {
+    pub fn new(authority: &Pubkey, index: Option<u64>) -> Self {
+        Self {
+            authority: *authority,
+            index: index.map(u64::to_le_bytes),
+            bump: None,
+        }
+    }
+
+    pub fn with_bump(mut self, bump: u8) -> Self {
+        self.bump = Some([bump]);
+        self
+    }
+
+    pub fn seeds(&self) -> Vec<&[u8]> {
+        let mut seeds = vec![ROOSTER_PREFIX, self.authority.as_ref()];
+
+        if let Some(index) = &self.index {
+            seeds.push(index);
+        }
+
+        if let Some(bump) = &self.bump {
+            seeds.push(bump);
+        }
+
+        seeds
+    }
 }
@@ -1,10 +1,49 @@
-use mpl_token_metadata::instruction::builders::{DelegateBuilder, LockBuilder, UnlockBuilder};
-use solana_program::program::{invoke, invoke_signed};
+use borsh::BorshSerialize;
+use mpl_token_metadata::instruction::builders::{
+    BurnBuilder, CreateBuilder, DelegateBuilder, LockBuilder, MintBuilder, PrintBuilder,
+    RevokeBuilder, UnlockBuilder, UnverifyBuilder, UpdateBuilder, VerifyBuilder,
+};
+use mpl_token_metadata::state::{AssetData, TokenStandard};
+use solana_program::{
+    hash::hash,
+    program::{invoke, invoke_signed},
+    program_pack::Pack,
+    rent::Rent,
+    system_instruction,
+    sysvar::{clock::Clock, Sysvar},
+};
 
 use crate::{
-    assertions::assert_rooster_pda,
-    instruction::{DelegateArgs, DelegateTransferArgs, LockArgs, UnlockArgs, WithdrawArgs},
-    state::Rooster,
+    assertions::{
+        assert_associated_token_account, assert_burnable_token_account, assert_edition_derivation,
+        assert_legacy_bump, assert_metadata_derivation, assert_multisig, assert_multisig_config,
+        assert_not_paused, assert_owned_by, assert_program_id, assert_rooster_authority,
+        assert_rooster_derivation, assert_rooster_pda, assert_rule_set, assert_signer,
+        assert_sysvar_instructions, assert_token_account, assert_token_account_owner,
+        assert_token_record_derivation, assert_withdraw_request,
+        assert_withdraw_request_derivation,
+    },
+    context::{
+        AcceptAuthorityAccounts, BurnAccounts, CancelWithdrawAccounts, CloseAccounts,
+        CreateProgrammableAccounts, DelegateAccounts, DelegateTransferAccounts, DepositAccounts,
+        InitAccounts, LockAccounts, MetadataDelegateAccounts, MigrateRoosterAccounts,
+        PrintEditionAccounts, ProgrammableLockAccounts, QueueWithdrawAccounts, RevokeAccounts,
+        RoosterAuthorityAccounts, SetPauseAccounts, UpdateMetadataAccounts, VerificationAccounts,
+        WithdrawAccounts,
+    },
+    instruction::{
+        BurnArgs, CloseArgs, CreateProgrammableArgs, DelegateArgs, DelegateMetadataArgs,
+        DelegateRole, DelegateTransferArgs, DepositArgs, InitArgs, LockArgs, MetadataDelegate,
+        PrintEditionArgs, ProposeAuthorityArgs, RevokeArgs, RevokeMetadataArgs, SetGuardianArgs,
+        SetMultisigArgs, SetPauseArgs, SetWithdrawDelayArgs, UnlockArgs, UpdateMetadataArgs,
+        VerificationArgs, WithdrawArgs,
+    },
+    pda::{find_edition_marker_account, RoosterSeeds, WITHDRAW_REQUEST_PREFIX},
+    state::{
+        Key, Rooster, RoosterV1, WithdrawRequest, PAUSE_ALL, PAUSE_DELEGATE, PAUSE_FLAGS,
+        PAUSE_LOCK, PAUSE_UNLOCK, PAUSE_WITHDRAW, ROOSTER_LEN, ROOSTER_V1_LEN, ROOSTER_VERSION,
+        WITHDRAW_REQUEST_LEN,
+    },
 };
 
 use super::*;
@@ -18,56 +57,95 @@ impl Processor {
     ) -> ProgramResult {
         let instruction: RoosterCommand = RoosterCommand::try_from_slice(instruction_data)?;
         match instruction {
-            RoosterCommand::Init => init(program_id, accounts),
+            RoosterCommand::Init => init(program_id, accounts, InitArgs { index: None }),
             RoosterCommand::Withdraw(args) => withdraw(program_id, accounts, args),
-            RoosterCommand::Delegate(args) => delegate(program_id, accounts, args),
-            RoosterCommand::Lock(args) => lock(program_id, accounts, args),
-            RoosterCommand::Unlock(args) => unlock(program_id, accounts, args),
-            RoosterCommand::ProgrammableLock(args) => programmable_lock(program_id, accounts, args),
-            RoosterCommand::ProgrammableUnlock(args) => {
-                programmable_unlock(program_id, accounts, args)
+            RoosterCommand::DeprecatedDelegate(args) => {
+                let bump = Some(args.bump);
+                delegate(program_id, accounts, args.into(), bump)
+            }
+            RoosterCommand::DeprecatedLock(args) => {
+                let bump = Some(args.bump);
+                lock(program_id, accounts, args.into(), bump)
+            }
+            RoosterCommand::DeprecatedUnlock(args) => {
+                let bump = Some(args.bump);
+                unlock(program_id, accounts, args.into(), bump)
+            }
+            RoosterCommand::DeprecatedProgrammableLock(args) => {
+                let bump = Some(args.bump);
+                programmable_lock(program_id, accounts, args.into(), bump)
+            }
+            RoosterCommand::DeprecatedProgrammableUnlock(args) => {
+                let bump = Some(args.bump);
+                programmable_unlock(program_id, accounts, args.into(), bump)
             }
             RoosterCommand::DelegateTransfer(args) => delegate_transfer(program_id, accounts, args),
+            RoosterCommand::Deposit(args) => deposit(program_id, accounts, args),
+            RoosterCommand::Delegate(args) => delegate(program_id, accounts, args, None),
+            RoosterCommand::Lock(args) => lock(program_id, accounts, args, None),
+            RoosterCommand::Unlock(args) => unlock(program_id, accounts, args, None),
+            RoosterCommand::ProgrammableLock(args) => {
+                programmable_lock(program_id, accounts, args, None)
+            }
+            RoosterCommand::ProgrammableUnlock(args) => {
+                programmable_unlock(program_id, accounts, args, None)
+            }
+            RoosterCommand::MigrateRooster => migrate_rooster(program_id, accounts),
+            RoosterCommand::ProposeAuthority(args) => propose_authority(program_id, accounts, args),
+            RoosterCommand::AcceptAuthority => accept_authority(program_id, accounts),
+            RoosterCommand::SetMultisig(args) => set_multisig(program_id, accounts, args),
+            RoosterCommand::Close(args) => close(program_id, accounts, args),
+            RoosterCommand::SetWithdrawDelay(args) => {
+                set_withdraw_delay(program_id, accounts, args)
+            }
+            RoosterCommand::QueueWithdraw(args) => queue_withdraw(program_id, accounts, args),
+            RoosterCommand::ExecuteWithdraw(args) => execute_withdraw(program_id, accounts, args),
+            RoosterCommand::CancelWithdraw => cancel_withdraw(program_id, accounts),
+            RoosterCommand::SetGuardian(args) => set_guardian(program_id, accounts, args),
+            RoosterCommand::SetPause(args) => set_pause(program_id, accounts, args),
+            RoosterCommand::Revoke(args) => revoke(program_id, accounts, args),
+            RoosterCommand::Burn(args) => burn(program_id, accounts, args),
+            RoosterCommand::CreateProgrammable(args) => {
+                create_programmable(program_id, accounts, args)
+            }
+            RoosterCommand::UpdateMetadata(args) => update_metadata(program_id, accounts, args),
+            RoosterCommand::DelegateMetadata(args) => delegate_metadata(program_id, accounts, args),
+            RoosterCommand::RevokeMetadata(args) => revoke_metadata(program_id, accounts, args),
+            RoosterCommand::Verify(args) => verify(program_id, accounts, args),
+            RoosterCommand::Unverify(args) => unverify(program_id, accounts, args),
+            RoosterCommand::PrintEdition(args) => print_edition(program_id, accounts, args),
+            RoosterCommand::InitIndexed(args) => init(program_id, accounts, args),
         }
     }
 }
 
-fn init(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+fn init(program_id: &Pubkey, accounts: &[AccountInfo], args: InitArgs) -> ProgramResult {
     msg!("Rooster: Init");
 
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let rooster_pda_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
+    let InitAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        system_program: system_program_info,
+        ..
+    } = InitAccounts::context(accounts)?;
 
-    if !authority_info.is_signer {
-        return Err(Crows::NotASigner.into());
-    }
-
-    let bump = assert_rooster_pda(rooster_pda_info, authority_info)?;
-    let rooster = Rooster::new(bump);
-    let rooster_signer_seeds = &[b"rooster", authority_info.key.as_ref(), &[bump]];
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
 
-    let serialized_rooster = rooster.try_to_vec()?;
-    let data_len = serialized_rooster.len();
+    let bump = assert_rooster_derivation(rooster_pda_info, authority_info.key, args.index)?;
+    let rooster = Rooster::new(bump, *authority_info.key, args.index, Clock::get()?.slot);
+    let rooster_seeds = rooster.seeds();
 
     mpl_utils::create_or_allocate_account_raw(
         *program_id,
         rooster_pda_info,
         system_program_info,
         authority_info,
-        data_len,
-        rooster_signer_seeds,
+        ROOSTER_LEN,
+        &rooster_seeds.seeds(),
     )?;
 
     msg!("Writing state");
-    sol_memcpy(
-        &mut rooster_pda_info.data.borrow_mut(),
-        serialized_rooster.as_slice(),
-        data_len,
-    );
-
-    Ok(())
+    rooster.save(rooster_pda_info)
 }
 
 pub fn withdraw(
@@ -77,30 +155,77 @@ pub fn withdraw(
 ) -> ProgramResult {
     msg!("Rooster: Withdraw");
 
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let rooster_pda_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let destination_owner_info = next_account_info(account_iter)?;
-    let destination_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let owner_token_record_info = next_account_info(account_iter)?;
-    let destination_token_record_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-    let spl_ata_program_info = next_account_info(account_iter)?;
-    let mpl_token_auth_rules_program_info = next_account_info(account_iter)?;
-    let rule_set_info = next_account_info(account_iter)?;
-
-    let bump = assert_rooster_pda(rooster_pda_info, authority_info)?;
-    let signer_seeds = &[b"rooster", authority_info.key.as_ref(), &[bump]];
+    withdraw_token(accounts, args.auth_data, false)
+}
+
+/// Transfers the token out of the rooster. Direct withdrawals need the multisig approval
+/// and are rejected on timelocked roosters; queued ones were approved when queued.
+fn withdraw_token(
+    accounts: &[AccountInfo],
+    auth_data: AuthorizationData,
+    queued: bool,
+) -> ProgramResult {
+    let WithdrawAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        token: token_info,
+        destination_owner: destination_owner_info,
+        destination: destination_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        owner_token_record: owner_token_record_info,
+        destination_token_record: destination_token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        spl_ata_program: spl_ata_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        remaining,
+    } = WithdrawAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_WITHDRAW)?;
+
+    if !queued {
+        assert_multisig(&rooster, remaining)?;
+
+        if rooster.withdraw_delay > 0 {
+            msg!("Withdrawals from this rooster must be queued");
+            return Err(Crows::WithdrawTimelocked.into());
+        }
+    }
+
+    assert_token_account(token_info, mint_info.key, rooster_pda_info.key)?;
+    assert_associated_token_account(destination_info, destination_owner_info.key, mint_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(owner_token_record_info, mint_info.key, token_info.key)?;
+    assert_token_record_derivation(
+        destination_token_record_info,
+        mint_info.key,
+        destination_info.key,
+    )?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(spl_ata_program_info, &SPL_ATA_TOKEN_PROGRAM_ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     let transfer_args = TransferArgs::V1 {
-        authorization_data: Some(args.auth_data),
+        authorization_data: Some(auth_data),
         amount: 1,
     };
 
@@ -117,11 +242,15 @@ pub fn withdraw(
         .edition(*edition_info.key)
         .owner_token_record(*owner_token_record_info.key)
         .destination_token_record(*destination_token_record_info.key)
-        .authorization_rules(*rule_set_info.key)
-        .authorization_rules_program(*mpl_token_auth_rules_program_info.key)
         .spl_token_program(*spl_token_program_info.key)
         .payer(*authority_info.key);
 
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
     msg!("building transfer instruction");
     let build_result = builder.build(transfer_args);
 
@@ -157,43 +286,107 @@ pub fn withdraw(
     ];
 
     msg!("invoking transfer instruction");
-    invoke_signed(&instruction, &account_infos, &[signer_seeds]).unwrap();
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking withdraw transfer instruction: {:?}", err);
+        err
+    })?;
 
-    Ok(())
+    rooster.withdrawals = rooster.withdrawals.saturating_add(1);
+
+    if queued {
+        rooster.queued_withdrawals = rooster.queued_withdrawals.saturating_sub(1);
+    }
+
+    rooster.save(rooster_pda_info)
 }
 
 pub fn delegate(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: DelegateArgs,
+    legacy_bump: Option<u8>,
 ) -> ProgramResult {
     msg!("Rooster: Delegate");
     let DelegateArgs {
         amount,
-        authority,
-        bump,
+        role,
+        auth_data,
     } = args;
 
-    let account_iter = &mut accounts.iter();
-    let delegate_info = next_account_info(account_iter)?;
-    let rooster_pda_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let token_record_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-    let mpl_token_auth_rules_program_info = next_account_info(account_iter)?;
-    let rule_set_info = next_account_info(account_iter)?;
-
-    let signer_seeds = &[b"rooster", authority.as_ref(), &[bump]];
-
-    let delegate_args = mpl_token_metadata::instruction::DelegateArgs::TransferV1 {
-        amount,
-        authorization_data: None,
+    let DelegateAccounts {
+        authority: authority_info,
+        delegate: delegate_info,
+        rooster_pda: rooster_pda_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        remaining,
+    } = DelegateAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    let bump = assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_DELEGATE)?;
+    assert_multisig(&rooster, remaining)?;
+    assert_legacy_bump(legacy_bump, bump)?;
+
+    // every role but Staking can move or burn the token, bypassing the withdraw queue
+    if rooster.withdraw_delay > 0 && !matches!(role, DelegateRole::Staking) {
+        msg!("Only Staking delegates can be approved on timelocked roosters");
+        return Err(Crows::WithdrawTimelocked.into());
+    }
+
+    assert_token_account(token_info, mint_info.key, rooster_pda_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
+
+    let delegate_args = match role {
+        DelegateRole::Transfer => mpl_token_metadata::instruction::DelegateArgs::TransferV1 {
+            amount,
+            authorization_data: auth_data,
+        },
+        DelegateRole::Sale => mpl_token_metadata::instruction::DelegateArgs::SaleV1 {
+            amount,
+            authorization_data: auth_data,
+        },
+        DelegateRole::Staking => mpl_token_metadata::instruction::DelegateArgs::StakingV1 {
+            amount,
+            authorization_data: auth_data,
+        },
+        DelegateRole::Utility => mpl_token_metadata::instruction::DelegateArgs::UtilityV1 {
+            amount,
+            authorization_data: auth_data,
+        },
+        // the locked address is deprecated in Token Metadata, which still stores it in the token record
+        #[allow(deprecated)]
+        DelegateRole::LockedTransfer { locked_address } => {
+            mpl_token_metadata::instruction::DelegateArgs::LockedTransferV1 {
+                amount,
+                locked_address,
+                authorization_data: auth_data,
+            }
+        }
     };
 
     let mut builder = DelegateBuilder::new();
@@ -205,10 +398,14 @@ pub fn delegate(
         .mint(*mint_info.key)
         .metadata(*metadata_info.key)
         .master_edition(*edition_info.key)
-        .authorization_rules(*rule_set_info.key)
-        .authorization_rules_program(*mpl_token_auth_rules_program_info.key)
         .spl_token_program(*spl_token_program_info.key)
-        .payer(*delegate_info.key);
+        .payer(*authority_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
 
     let build_result = builder.build(delegate_args);
 
@@ -221,6 +418,7 @@ pub fn delegate(
     };
 
     let account_infos = [
+        authority_info.clone(),
         delegate_info.clone(),
         token_record_info.clone(),
         token_info.clone(),
@@ -235,34 +433,60 @@ pub fn delegate(
         rule_set_info.clone(),
     ];
 
-    invoke_signed(&instruction, &account_infos, &[signer_seeds]).unwrap();
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking delegate instruction: {:?}", err);
+        err
+    })?;
 
-    Ok(())
+    rooster.delegations = rooster.delegations.saturating_add(1);
+    rooster.save(rooster_pda_info)
 }
 
-pub fn lock(_program_id: &Pubkey, accounts: &[AccountInfo], args: LockArgs) -> ProgramResult {
+pub fn lock(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: LockArgs,
+    legacy_bump: Option<u8>,
+) -> ProgramResult {
     msg!("Rooster: Lock");
-    let LockArgs { amount, bump } = args;
-
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let token_owner_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-
-    let signer_seeds = &[b"rooster", token_owner_info.key.as_ref(), &[bump]];
+    let LockArgs { amount, auth_data } = args;
+
+    let LockAccounts {
+        delegate: authority_info,
+        token_owner: token_owner_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        ..
+    } = LockAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(authority_info)?;
+    assert_rooster_authority(&rooster, token_owner_info)?;
+    let bump = assert_rooster_pda(authority_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_LOCK)?;
+    assert_legacy_bump(legacy_bump, bump)?;
+
+    assert_token_account(token_info, mint_info.key, token_owner_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     // creates a delegate to lock the token
 
     let delegate_args = mpl_token_metadata::instruction::DelegateArgs::UtilityV1 {
         amount,
-        authorization_data: None,
+        authorization_data: auth_data.clone(),
     };
 
     let build_result = DelegateBuilder::new()
@@ -297,12 +521,15 @@ pub fn lock(_program_id: &Pubkey, accounts: &[AccountInfo], args: LockArgs) -> P
         spl_token_program_info.clone(),
     ];
 
-    invoke(&instruction, &account_infos).unwrap();
+    invoke(&instruction, &account_infos).map_err(|err| {
+        msg!("Error invoking lock delegate instruction: {:?}", err);
+        err
+    })?;
 
     // locks the token
 
     let lock_args = mpl_token_metadata::instruction::LockArgs::V1 {
-        authorization_data: None,
+        authorization_data: auth_data,
     };
 
     let build_result = LockBuilder::new()
@@ -335,31 +562,57 @@ pub fn lock(_program_id: &Pubkey, accounts: &[AccountInfo], args: LockArgs) -> P
         spl_token_program_info.clone(),
     ];
 
-    invoke_signed(&instruction, &account_infos, &[signer_seeds])
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking lock instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
 }
 
-pub fn unlock(_program_id: &Pubkey, accounts: &[AccountInfo], args: UnlockArgs) -> ProgramResult {
+pub fn unlock(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UnlockArgs,
+    legacy_bump: Option<u8>,
+) -> ProgramResult {
     msg!("Rooster: Unlock");
-    let UnlockArgs { bump } = args;
-
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let token_owner_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-
-    let signer_seeds = &[b"rooster", token_owner_info.key.as_ref(), &[bump]];
+
+    let LockAccounts {
+        delegate: authority_info,
+        token_owner: token_owner_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        ..
+    } = LockAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(authority_info)?;
+    assert_rooster_authority(&rooster, token_owner_info)?;
+    let bump = assert_rooster_pda(authority_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_UNLOCK)?;
+    assert_legacy_bump(legacy_bump, bump)?;
+
+    assert_token_account(token_info, mint_info.key, token_owner_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     // unlocks the token (must have been locked by rooster)
 
     let unlock_args = mpl_token_metadata::instruction::UnlockArgs::V1 {
-        authorization_data: None,
+        authorization_data: args.auth_data,
     };
 
     let build_result = UnlockBuilder::new()
@@ -393,42 +646,72 @@ pub fn unlock(_program_id: &Pubkey, accounts: &[AccountInfo], args: UnlockArgs)
         spl_token_program_info.clone(),
     ];
 
-    invoke_signed(&instruction, &account_infos, &[signer_seeds])
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking unlock instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
 }
 
 pub fn programmable_lock(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: LockArgs,
+    legacy_bump: Option<u8>,
 ) -> ProgramResult {
     msg!("Rooster: Programmable Lock");
-    let LockArgs { amount, bump } = args;
-
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let token_owner_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let token_record_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-    let _mpl_token_auth_rules_program_info = next_account_info(account_iter)?;
-    let rule_set_info = next_account_info(account_iter)?;
-
-    let signer_seeds = &[b"rooster", token_owner_info.key.as_ref(), &[bump]];
+    let LockArgs { amount, auth_data } = args;
+
+    let ProgrammableLockAccounts {
+        delegate: authority_info,
+        token_owner: token_owner_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = ProgrammableLockAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(authority_info)?;
+    assert_rooster_authority(&rooster, token_owner_info)?;
+    let bump = assert_rooster_pda(authority_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_LOCK)?;
+    assert_legacy_bump(legacy_bump, bump)?;
+
+    assert_token_account(token_info, mint_info.key, token_owner_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     // creates a delegate to lock the token
 
     let delegate_args = mpl_token_metadata::instruction::DelegateArgs::UtilityV1 {
         amount,
-        authorization_data: None,
+        authorization_data: auth_data.clone(),
     };
 
-    let build_result = DelegateBuilder::new()
+    let mut builder = DelegateBuilder::new();
+    builder
         .authority(*token_owner_info.key)
         .delegate(*authority_info.key)
         .token(*token_info.key)
@@ -436,10 +719,16 @@ pub fn programmable_lock(
         .metadata(*metadata_info.key)
         .master_edition(*edition_info.key)
         .token_record(*token_record_info.key)
-        .authorization_rules(*rule_set_info.key)
         .payer(*token_owner_info.key)
-        .spl_token_program(*spl_token_program_info.key)
-        .build(delegate_args);
+        .spl_token_program(*spl_token_program_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let build_result = builder.build(delegate_args);
 
     let instruction = match build_result {
         Ok(delegate) => delegate.instruction(),
@@ -461,15 +750,22 @@ pub fn programmable_lock(
         system_program_info.clone(),
         sysvar_instructions_info.clone(),
         spl_token_program_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
         rule_set_info.clone(),
     ];
 
-    invoke(&instruction, &account_infos).unwrap();
+    invoke(&instruction, &account_infos).map_err(|err| {
+        msg!(
+            "Error invoking programmable lock delegate instruction: {:?}",
+            err
+        );
+        err
+    })?;
 
     // locks the token
 
     let lock_args = mpl_token_metadata::instruction::LockArgs::V1 {
-        authorization_data: None,
+        authorization_data: auth_data,
     };
 
     let build_result = LockBuilder::new()
@@ -505,51 +801,86 @@ pub fn programmable_lock(
         spl_token_program_info.clone(),
     ];
 
-    invoke_signed(&instruction, &account_infos, &[signer_seeds])
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking programmable lock instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
 }
 
 pub fn programmable_unlock(
     _program_id: &Pubkey,
     accounts: &[AccountInfo],
     args: UnlockArgs,
+    legacy_bump: Option<u8>,
 ) -> ProgramResult {
     msg!("Rooster: Programmable Unlock");
-    let UnlockArgs { bump } = args;
-
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let token_owner_info = next_account_info(account_iter)?;
-    let token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let token_record_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-    let _mpl_token_auth_rules_program_info = next_account_info(account_iter)?;
-    let rule_set_info = next_account_info(account_iter)?;
-
-    let signer_seeds = &[b"rooster", token_owner_info.key.as_ref(), &[bump]];
+
+    let ProgrammableLockAccounts {
+        delegate: authority_info,
+        token_owner: token_owner_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = ProgrammableLockAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(authority_info)?;
+    assert_rooster_authority(&rooster, token_owner_info)?;
+    let bump = assert_rooster_pda(authority_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_UNLOCK)?;
+    assert_legacy_bump(legacy_bump, bump)?;
+
+    assert_token_account(token_info, mint_info.key, token_owner_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     // unlocks the token (the token must the locked by rooster)
 
     let unlock_args = mpl_token_metadata::instruction::UnlockArgs::V1 {
-        authorization_data: None,
+        authorization_data: args.auth_data,
     };
 
-    let build_result = UnlockBuilder::new()
+    let mut builder = UnlockBuilder::new();
+    builder
         .authority(*authority_info.key)
         .token(*token_info.key)
         .mint(*mint_info.key)
         .metadata(*metadata_info.key)
         .edition(*edition_info.key)
         .token_record(*token_record_info.key)
-        .authorization_rules(*rule_set_info.key)
         .payer(*token_owner_info.key)
-        .spl_token_program(*spl_token_program_info.key)
-        .build(unlock_args);
+        .spl_token_program(*spl_token_program_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let build_result = builder.build(unlock_args);
 
     let instruction = match build_result {
         Ok(unlock) => unlock.instruction(),
@@ -571,9 +902,16 @@ pub fn programmable_unlock(
         system_program_info.clone(),
         sysvar_instructions_info.clone(),
         spl_token_program_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
     ];
 
-    invoke_signed(&instruction, &account_infos, &[signer_seeds])
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking programmable unlock instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
 }
 
 pub fn delegate_transfer(
@@ -583,28 +921,65 @@ pub fn delegate_transfer(
 ) -> ProgramResult {
     msg!("Rooster: DelegateTransfer");
 
-    let account_iter = &mut accounts.iter();
-    let authority_info = next_account_info(account_iter)?;
-    let rooster_pda_info = next_account_info(account_iter)?;
-    let source_owner_info = next_account_info(account_iter)?;
-    let source_token_info = next_account_info(account_iter)?;
-    let destination_owner_info = next_account_info(account_iter)?;
-    let destination_token_info = next_account_info(account_iter)?;
-    let mint_info = next_account_info(account_iter)?;
-    let metadata_info = next_account_info(account_iter)?;
-    let edition_info = next_account_info(account_iter)?;
-    let source_token_record_info = next_account_info(account_iter)?;
-    let destination_token_record_info = next_account_info(account_iter)?;
-    let token_metadata_program_info = next_account_info(account_iter)?;
-    let system_program_info = next_account_info(account_iter)?;
-    let sysvar_instructions_info = next_account_info(account_iter)?;
-    let spl_token_program_info = next_account_info(account_iter)?;
-    let spl_ata_program_info = next_account_info(account_iter)?;
-    let mpl_token_auth_rules_program_info = next_account_info(account_iter)?;
-    let rule_set_info = next_account_info(account_iter)?;
-
-    let bump = assert_rooster_pda(rooster_pda_info, authority_info)?;
-    let signer_seeds = &[b"rooster", authority_info.key.as_ref(), &[bump]];
+    let DelegateTransferAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        source_owner: source_owner_info,
+        source_token: source_token_info,
+        destination_owner: destination_owner_info,
+        destination_token: destination_token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        source_token_record: source_token_record_info,
+        destination_token_record: destination_token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        spl_ata_program: spl_ata_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        remaining,
+    } = DelegateTransferAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_DELEGATE)?;
+    assert_multisig(&rooster, remaining)?;
+
+    assert_token_account(source_token_info, mint_info.key, source_owner_info.key)?;
+    assert_associated_token_account(
+        destination_token_info,
+        destination_owner_info.key,
+        mint_info.key,
+    )?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(
+        source_token_record_info,
+        mint_info.key,
+        source_token_info.key,
+    )?;
+    assert_token_record_derivation(
+        destination_token_record_info,
+        mint_info.key,
+        destination_token_info.key,
+    )?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(spl_ata_program_info, &SPL_ATA_TOKEN_PROGRAM_ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
 
     let transfer_args = TransferArgs::V1 {
         authorization_data: Some(args.auth_data),
@@ -624,11 +999,15 @@ pub fn delegate_transfer(
         .edition(*edition_info.key)
         .owner_token_record(*source_token_record_info.key)
         .destination_token_record(*destination_token_record_info.key)
-        .authorization_rules(*rule_set_info.key)
-        .authorization_rules_program(*mpl_token_auth_rules_program_info.key)
         .spl_token_program(*spl_token_program_info.key)
         .payer(*authority_info.key);
 
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
     msg!("building transfer instruction");
     let build_result = builder.build(transfer_args);
 
@@ -665,7 +1044,1787 @@ pub fn delegate_transfer(
     ];
 
     msg!("invoking transfer instruction");
-    invoke_signed(&instruction, &account_infos, &[signer_seeds]).unwrap();
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking delegate transfer instruction: {:?}", err);
+        err
+    })?;
+
+    rooster.delegate_transfers = rooster.delegate_transfers.saturating_add(1);
+    rooster.save(rooster_pda_info)
+}
+
+pub fn deposit(_program_id: &Pubkey, accounts: &[AccountInfo], args: DepositArgs) -> ProgramResult {
+    msg!("Rooster: Deposit");
+
+    let DepositAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        token: token_info,
+        destination: destination_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        owner_token_record: owner_token_record_info,
+        destination_token_record: destination_token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        spl_ata_program: spl_ata_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = DepositAccounts::context(accounts)?;
+
+    // the destination token account must belong to the authority's rooster
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+
+    assert_token_account(token_info, mint_info.key, authority_info.key)?;
+    assert_associated_token_account(destination_info, rooster_pda_info.key, mint_info.key)?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_token_record_derivation(owner_token_record_info, mint_info.key, token_info.key)?;
+    assert_token_record_derivation(
+        destination_token_record_info,
+        mint_info.key,
+        destination_info.key,
+    )?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(spl_ata_program_info, &SPL_ATA_TOKEN_PROGRAM_ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let transfer_args = TransferArgs::V1 {
+        authorization_data: Some(args.auth_data),
+        amount: 1,
+    };
+
+    msg!("setting up builder");
+    let mut builder = TransferBuilder::new();
+    builder
+        .authority(*authority_info.key)
+        .token_owner(*authority_info.key)
+        .token(*token_info.key)
+        .destination_owner(*rooster_pda_info.key)
+        .destination(*destination_info.key)
+        .mint(*mint_info.key)
+        .metadata(*metadata_info.key)
+        .edition(*edition_info.key)
+        .owner_token_record(*owner_token_record_info.key)
+        .destination_token_record(*destination_token_record_info.key)
+        .spl_token_program(*spl_token_program_info.key)
+        .payer(*authority_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    msg!("building transfer instruction");
+    let build_result = builder.build(transfer_args);
+
+    let instruction = match build_result {
+        Ok(transfer) => {
+            msg!("transfer instruction built");
+            transfer.instruction()
+        }
+        Err(err) => {
+            msg!("Error building transfer instruction: {:?}", err);
+            return Err(Crows::TransferBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        authority_info.clone(),
+        token_info.clone(),
+        rooster_pda_info.clone(),
+        destination_info.clone(),
+        mint_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        owner_token_record_info.clone(),
+        destination_token_record_info.clone(),
+        rule_set_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        spl_token_program_info.clone(),
+        spl_ata_program_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+    ];
+
+    msg!("invoking transfer instruction");
+    invoke(&instruction, &account_infos).map_err(|err| {
+        msg!("Error invoking deposit transfer instruction: {:?}", err);
+        err
+    })?;
+
+    rooster.deposits = rooster.deposits.saturating_add(1);
+    rooster.save(rooster_pda_info)
+}
+
+fn migrate_rooster(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Rooster: MigrateRooster");
+
+    let MigrateRoosterAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        system_program: system_program_info,
+        ..
+    } = MigrateRoosterAccounts::context(accounts)?;
+
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+
+    if rooster_pda_info.data_len() != ROOSTER_V1_LEN {
+        let mut rooster = Rooster::from_account_info_unversioned(rooster_pda_info)?;
+
+        match rooster.version {
+            ROOSTER_VERSION => {
+                assert_rooster_authority(&rooster, authority_info)?;
+                assert_rooster_pda(rooster_pda_info, &rooster)?;
+                msg!("Rooster is already at version {}", ROOSTER_VERSION);
+                return Ok(());
+            }
+            // version 2 authorities were fixed and stored in the seed position
+            2 => {
+                rooster.authority = rooster.seed;
+                rooster.pending_authority = None;
+                rooster.version = ROOSTER_VERSION;
+            }
+            _ => return Err(Crows::UnsupportedVersion.into()),
+        }
+
+        assert_rooster_authority(&rooster, authority_info)?;
+        assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+        msg!("Writing state");
+        return rooster.save(rooster_pda_info);
+    }
+
+    // version 1 roosters are never indexed and their authority is the seed
+    let bump = assert_rooster_derivation(rooster_pda_info, authority_info.key, None)?;
+
+    let rooster_v1 = RoosterV1::from_account_info(rooster_pda_info)?;
+
+    if rooster_v1.bump != bump {
+        return Err(Crows::InvalidBump.into());
+    }
+
+    // tops up the rent for the new account size before reallocating
+    let lamports = Rent::get()?
+        .minimum_balance(ROOSTER_LEN)
+        .saturating_sub(rooster_pda_info.lamports());
+
+    if lamports > 0 {
+        invoke(
+            &system_instruction::transfer(authority_info.key, rooster_pda_info.key, lamports),
+            &[
+                authority_info.clone(),
+                rooster_pda_info.clone(),
+                system_program_info.clone(),
+            ],
+        )
+        .map_err(|err| {
+            msg!("Error invoking rent transfer instruction: {:?}", err);
+            err
+        })?;
+    }
+
+    rooster_pda_info.realloc(ROOSTER_LEN, true)?;
+
+    // the creation slot of a version 1 rooster is unknown, so the migration slot is used
+    let rooster = Rooster::new(bump, *authority_info.key, None, Clock::get()?.slot);
+
+    msg!("Writing state");
+    rooster.save(rooster_pda_info)
+}
+
+fn close(_program_id: &Pubkey, accounts: &[AccountInfo], args: CloseArgs) -> ProgramResult {
+    msg!("Rooster: Close");
+
+    let CloseAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        recipient: recipient_info,
+        spl_token_program: spl_token_program_info,
+        remaining,
+    } = CloseAccounts::context(accounts)?;
+
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+
+    if recipient_info.key == rooster_pda_info.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // both the current and the version 1 (never indexed) layouts can be closed
+    let rooster_seeds = if rooster_pda_info.data_len() == ROOSTER_V1_LEN {
+        RoosterV1::from_account_info(rooster_pda_info)?;
+        let bump = assert_rooster_derivation(rooster_pda_info, authority_info.key, None)?;
+        RoosterSeeds::new(authority_info.key, None).with_bump(bump)
+    } else {
+        let rooster = Rooster::from_account_info(rooster_pda_info)?;
+        assert_rooster_authority(&rooster, authority_info)?;
+        assert_rooster_pda(rooster_pda_info, &rooster)?;
+        assert_multisig(&rooster, remaining)?;
+
+        // the withdraw requests would outlive the rooster they withdraw from
+        if rooster.queued_withdrawals > 0 {
+            msg!(
+                "Rooster has {} queued withdrawals",
+                rooster.queued_withdrawals
+            );
+            return Err(Crows::WithdrawalsQueued.into());
+        }
+
+        rooster.seeds()
+    };
+    let signer_seeds = rooster_seeds.seeds();
+
+    // only the token accounts passed are checked, the multisig signers follow them
+    for token_info in remaining.iter().filter(|info| !info.is_signer) {
+        let token = assert_token_account_owner(token_info, rooster_pda_info.key)?;
+
+        if token.amount > 0 {
+            msg!("Token account {} is not empty", token_info.key);
+            return Err(Crows::RoosterHoldsTokens.into());
+        }
+
+        if args.close_token_accounts {
+            let instruction = spl_token::instruction::close_account(
+                spl_token_program_info.key,
+                token_info.key,
+                recipient_info.key,
+                rooster_pda_info.key,
+                &[],
+            )?;
+
+            invoke_signed(
+                &instruction,
+                &[
+                    token_info.clone(),
+                    recipient_info.clone(),
+                    rooster_pda_info.clone(),
+                    spl_token_program_info.clone(),
+                ],
+                &[&signer_seeds],
+            )
+            .map_err(|err| {
+                msg!("Error invoking close account instruction: {:?}", err);
+                err
+            })?;
+        }
+    }
+
+    let recipient_lamports = recipient_info
+        .lamports()
+        .checked_add(rooster_pda_info.lamports())
+        .ok_or(Crows::NumericalOverflow)?;
+
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports;
+    **rooster_pda_info.try_borrow_mut_lamports()? = 0;
+    rooster_pda_info.try_borrow_mut_data()?.fill(0);
 
     Ok(())
 }
+
+fn propose_authority(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: ProposeAuthorityArgs,
+) -> ProgramResult {
+    msg!("Rooster: ProposeAuthority");
+
+    let RoosterAuthorityAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        ..
+    } = RoosterAuthorityAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    rooster.pending_authority = args.new_authority;
+    rooster.save(rooster_pda_info)
+}
+
+fn accept_authority(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Rooster: AcceptAuthority");
+
+    let AcceptAuthorityAccounts {
+        new_authority: new_authority_info,
+        rooster_pda: rooster_pda_info,
+        ..
+    } = AcceptAuthorityAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    if rooster.pending_authority != Some(*new_authority_info.key) {
+        return Err(Crows::InvalidPendingAuthority.into());
+    }
+
+    rooster.authority = *new_authority_info.key;
+    rooster.pending_authority = None;
+    rooster.save(rooster_pda_info)
+}
+
+fn set_multisig(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetMultisigArgs,
+) -> ProgramResult {
+    msg!("Rooster: SetMultisig");
+
+    let RoosterAuthorityAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        remaining,
+    } = RoosterAuthorityAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    // changing the configuration of a multisig rooster requires the current signers
+    assert_multisig(&rooster, remaining)?;
+    assert_multisig_config(&args.signers, args.threshold)?;
+
+    rooster.signers = args.signers;
+    rooster.threshold = args.threshold;
+    rooster.save(rooster_pda_info)
+}
+
+fn set_withdraw_delay(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetWithdrawDelayArgs,
+) -> ProgramResult {
+    msg!("Rooster: SetWithdrawDelay");
+
+    let RoosterAuthorityAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        remaining,
+    } = RoosterAuthorityAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_multisig(&rooster, remaining)?;
+
+    if args.withdraw_delay < 0 {
+        return Err(Crows::InvalidWithdrawDelay.into());
+    }
+
+    // already queued withdrawals keep the delay they were queued with
+    rooster.withdraw_delay = args.withdraw_delay;
+    rooster.save(rooster_pda_info)
+}
+
+fn queue_withdraw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: WithdrawArgs,
+) -> ProgramResult {
+    msg!("Rooster: QueueWithdraw");
+
+    let QueueWithdrawAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        withdraw_request: withdraw_request_info,
+        mint: mint_info,
+        destination_owner: destination_owner_info,
+        destination: destination_info,
+        system_program: system_program_info,
+        remaining,
+    } = QueueWithdrawAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_WITHDRAW)?;
+    assert_multisig(&rooster, remaining)?;
+    assert_associated_token_account(destination_info, destination_owner_info.key, mint_info.key)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+
+    let bump = assert_withdraw_request_derivation(
+        withdraw_request_info,
+        rooster_pda_info.key,
+        mint_info.key,
+    )?;
+
+    let queued_at = Clock::get()?.unix_timestamp;
+    let executable_at = queued_at
+        .checked_add(rooster.withdraw_delay)
+        .ok_or(Crows::NumericalOverflow)?;
+
+    let withdraw_request = WithdrawRequest {
+        key: Key::WithdrawRequest,
+        bump,
+        rooster: *rooster_pda_info.key,
+        mint: *mint_info.key,
+        destination_owner: *destination_owner_info.key,
+        destination: *destination_info.key,
+        auth_data_hash: hash_auth_data(&args.auth_data)?,
+        queued_at,
+        executable_at,
+    };
+
+    mpl_utils::create_or_allocate_account_raw(
+        *program_id,
+        withdraw_request_info,
+        system_program_info,
+        authority_info,
+        WITHDRAW_REQUEST_LEN,
+        &[
+            WITHDRAW_REQUEST_PREFIX,
+            rooster_pda_info.key.as_ref(),
+            mint_info.key.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    rooster.queued_withdrawals = rooster
+        .queued_withdrawals
+        .checked_add(1)
+        .ok_or(Crows::NumericalOverflow)?;
+    rooster.save(rooster_pda_info)?;
+
+    msg!("Withdrawal executable at {}", executable_at);
+    withdraw_request.save(withdraw_request_info)
+}
+
+fn execute_withdraw(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: WithdrawArgs,
+) -> ProgramResult {
+    msg!("Rooster: ExecuteWithdraw");
+
+    let (withdraw_request_info, withdraw_accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    let WithdrawAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        destination_owner: destination_owner_info,
+        destination: destination_info,
+        mint: mint_info,
+        ..
+    } = WithdrawAccounts::context(withdraw_accounts)?;
+
+    let withdraw_request = WithdrawRequest::from_account_info(withdraw_request_info)?;
+    assert_withdraw_request(
+        withdraw_request_info,
+        &withdraw_request,
+        rooster_pda_info.key,
+        mint_info.key,
+    )?;
+
+    if withdraw_request.destination_owner != *destination_owner_info.key
+        || withdraw_request.destination != *destination_info.key
+    {
+        return Err(Crows::InvalidWithdrawRequest.into());
+    }
+
+    if withdraw_request.auth_data_hash != hash_auth_data(&args.auth_data)? {
+        return Err(Crows::AuthorizationDataMismatch.into());
+    }
+
+    if Clock::get()?.unix_timestamp < withdraw_request.executable_at {
+        msg!(
+            "Withdrawal executable at {}",
+            withdraw_request.executable_at
+        );
+        return Err(Crows::WithdrawTimelocked.into());
+    }
+
+    withdraw_token(withdraw_accounts, args.auth_data, true)?;
+
+    close_withdraw_request(withdraw_request_info, authority_info)
+}
+
+fn cancel_withdraw(_program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    msg!("Rooster: CancelWithdraw");
+
+    let CancelWithdrawAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        withdraw_request: withdraw_request_info,
+        ..
+    } = CancelWithdrawAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    let withdraw_request = WithdrawRequest::from_account_info(withdraw_request_info)?;
+    assert_withdraw_request(
+        withdraw_request_info,
+        &withdraw_request,
+        rooster_pda_info.key,
+        &withdraw_request.mint,
+    )?;
+
+    rooster.queued_withdrawals = rooster.queued_withdrawals.saturating_sub(1);
+    rooster.save(rooster_pda_info)?;
+
+    close_withdraw_request(withdraw_request_info, authority_info)
+}
+
+fn set_guardian(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: SetGuardianArgs,
+) -> ProgramResult {
+    msg!("Rooster: SetGuardian");
+
+    let RoosterAuthorityAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        remaining,
+    } = RoosterAuthorityAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_multisig(&rooster, remaining)?;
+
+    rooster.guardian = args.guardian;
+    rooster.save(rooster_pda_info)
+}
+
+fn set_pause(_program_id: &Pubkey, accounts: &[AccountInfo], args: SetPauseArgs) -> ProgramResult {
+    msg!("Rooster: SetPause");
+
+    let SetPauseAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        ..
+    } = SetPauseAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    if rooster.guardian == Some(*authority_info.key) {
+        assert_signer(authority_info, Crows::GuardianNotSigner)?;
+    } else {
+        assert_signer(authority_info, Crows::AuthorityNotSigner)?;
+    }
+
+    if args.pause_flags & !PAUSE_FLAGS != 0 {
+        return Err(Crows::InvalidPauseFlags.into());
+    }
+
+    if *authority_info.key != rooster.authority {
+        if rooster.guardian != Some(*authority_info.key) {
+            return Err(Crows::InvalidAuthority.into());
+        }
+
+        // the guardian can only pause, lifting a pause is left to the authority
+        if args.pause_flags & rooster.pause_flags != rooster.pause_flags {
+            msg!("Only the authority can unpause the rooster");
+            return Err(Crows::InvalidAuthority.into());
+        }
+    }
+
+    rooster.pause_flags = args.pause_flags;
+    rooster.save(rooster_pda_info)
+}
+
+fn revoke(_program_id: &Pubkey, accounts: &[AccountInfo], args: RevokeArgs) -> ProgramResult {
+    msg!("Rooster: Revoke");
+
+    let RevokeAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        delegate: delegate_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = RevokeAccounts::context(accounts)?;
+
+    // revoking is not subject to the pause flags, so delegates can be cleared while paused
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    assert_owned_by(token_info, &spl_token::ID)?;
+
+    let token = spl_token::state::Account::unpack(&token_info.try_borrow_data()?)?;
+
+    if token.mint != *mint_info.key {
+        return Err(Crows::MintMismatch.into());
+    }
+
+    // vault tokens are owned by the rooster PDA, locked tokens by the rooster authority
+    let token_owner_info = if token.owner == *rooster_pda_info.key {
+        rooster_pda_info
+    } else if token.owner == *authority_info.key {
+        authority_info
+    } else {
+        return Err(Crows::InvalidTokenAccount.into());
+    };
+
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+
+    if *token_record_info.key != mpl_token_metadata::ID {
+        assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    }
+
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let revoke_args = match args.role {
+        TokenDelegateRole::Sale => mpl_token_metadata::instruction::RevokeArgs::SaleV1,
+        TokenDelegateRole::Transfer => mpl_token_metadata::instruction::RevokeArgs::TransferV1,
+        TokenDelegateRole::Utility => mpl_token_metadata::instruction::RevokeArgs::UtilityV1,
+        TokenDelegateRole::Staking => mpl_token_metadata::instruction::RevokeArgs::StakingV1,
+        TokenDelegateRole::Standard => mpl_token_metadata::instruction::RevokeArgs::StandardV1,
+        TokenDelegateRole::LockedTransfer => {
+            mpl_token_metadata::instruction::RevokeArgs::LockedTransferV1
+        }
+        TokenDelegateRole::Migration => mpl_token_metadata::instruction::RevokeArgs::MigrationV1,
+    };
+
+    let mut builder = RevokeBuilder::new();
+    builder
+        .delegate(*delegate_info.key)
+        .metadata(*metadata_info.key)
+        .master_edition(*edition_info.key)
+        .mint(*mint_info.key)
+        .token(*token_info.key)
+        .authority(*token_owner_info.key)
+        .payer(*authority_info.key)
+        .spl_token_program(*spl_token_program_info.key);
+
+    // legacy tokens have no token record, the Token Metadata program stands in for it
+    if *token_record_info.key != mpl_token_metadata::ID {
+        builder.token_record(*token_record_info.key);
+    }
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let instruction = match builder.build(revoke_args) {
+        Ok(revoke) => revoke.instruction(),
+        Err(err) => {
+            msg!("Error building revoke instruction: {:?}", err);
+            return Err(Crows::RevokeBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        delegate_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        token_record_info.clone(),
+        mint_info.clone(),
+        token_info.clone(),
+        authority_info.clone(),
+        rooster_pda_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        spl_token_program_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking revoke instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn burn(_program_id: &Pubkey, accounts: &[AccountInfo], args: BurnArgs) -> ProgramResult {
+    msg!("Rooster: Burn");
+
+    let BurnAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        collection_metadata: collection_metadata_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        mint: mint_info,
+        token: token_info,
+        master_edition: master_edition_info,
+        master_edition_mint: master_edition_mint_info,
+        master_edition_token: master_edition_token_info,
+        edition_marker: edition_marker_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        remaining,
+    } = BurnAccounts::context(accounts)?;
+
+    // burning takes the token out of the rooster, like a withdrawal
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_WITHDRAW)?;
+    assert_multisig(&rooster, remaining)?;
+
+    // burns cannot be queued, so timelocked roosters only release assets through withdrawals
+    if rooster.withdraw_delay > 0 {
+        msg!("Burning is disabled on timelocked roosters");
+        return Err(Crows::WithdrawTimelocked.into());
+    }
+
+    if *token_record_info.key != mpl_token_metadata::ID {
+        assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    }
+
+    assert_burnable_token_account(
+        token_info,
+        token_record_info,
+        mint_info.key,
+        rooster_pda_info.key,
+    )?;
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+
+    if *master_edition_info.key != mpl_token_metadata::ID {
+        assert_edition_derivation(master_edition_info, master_edition_mint_info.key)?;
+    }
+
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+
+    // the rooster PDA burns as either the token owner or its Utility delegate
+    let mut builder = BurnBuilder::new();
+    builder
+        .authority(*rooster_pda_info.key)
+        .metadata(*metadata_info.key)
+        .edition(*edition_info.key)
+        .mint(*mint_info.key)
+        .token(*token_info.key)
+        .spl_token_program(*spl_token_program_info.key);
+
+    // absent optional accounts are replaced by the Token Metadata program
+    if *collection_metadata_info.key != mpl_token_metadata::ID {
+        builder.collection_metadata(*collection_metadata_info.key);
+    }
+
+    if *master_edition_info.key != mpl_token_metadata::ID {
+        builder
+            .master_edition(*master_edition_info.key)
+            .master_edition_mint(*master_edition_mint_info.key)
+            .master_edition_token(*master_edition_token_info.key)
+            .edition_marker(*edition_marker_info.key);
+    }
+
+    if *token_record_info.key != mpl_token_metadata::ID {
+        builder.token_record(*token_record_info.key);
+    }
+
+    let burn_args = mpl_token_metadata::instruction::BurnArgs::V1 {
+        amount: args.amount,
+    };
+
+    let instruction = match builder.build(burn_args) {
+        Ok(burn) => burn.instruction(),
+        Err(err) => {
+            msg!("Error building burn instruction: {:?}", err);
+            return Err(Crows::BurnBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        rooster_pda_info.clone(),
+        collection_metadata_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        mint_info.clone(),
+        token_info.clone(),
+        master_edition_info.clone(),
+        master_edition_mint_info.clone(),
+        master_edition_token_info.clone(),
+        edition_marker_info.clone(),
+        token_record_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        spl_token_program_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking burn instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn create_programmable(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: CreateProgrammableArgs,
+) -> ProgramResult {
+    msg!("Rooster: CreateProgrammable");
+
+    let CreateProgrammableAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token: token_info,
+        token_record: token_record_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        spl_token_program: spl_token_program_info,
+        spl_ata_program: spl_ata_program_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = CreateProgrammableAccounts::context(accounts)?;
+
+    // minting into the rooster is a deposit
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_associated_token_account(token_info, rooster_pda_info.key, mint_info.key)?;
+    assert_token_record_derivation(token_record_info, mint_info.key, token_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(spl_ata_program_info, &SPL_ATA_TOKEN_PROGRAM_ID)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let rooster_seeds = rooster.seeds();
+    let signer_seeds = rooster_seeds.seeds();
+
+    let rule_set = if *rule_set_info.key != mpl_token_metadata::ID {
+        Some(*rule_set_info.key)
+    } else {
+        None
+    };
+
+    // creates the metadata and master edition, initializing the mint
+
+    let mut asset_data = AssetData::new(
+        TokenStandard::ProgrammableNonFungible,
+        args.name,
+        args.symbol,
+        args.uri,
+    );
+    asset_data.seller_fee_basis_points = args.seller_fee_basis_points;
+    asset_data.creators = args.creators;
+    asset_data.rule_set = rule_set;
+
+    let create_args = mpl_token_metadata::instruction::CreateArgs::V1 {
+        asset_data,
+        decimals: Some(0),
+        print_supply: Some(args.print_supply),
+    };
+
+    let build_result = CreateBuilder::new()
+        .metadata(*metadata_info.key)
+        .master_edition(*edition_info.key)
+        .mint(*mint_info.key)
+        .authority(*rooster_pda_info.key)
+        .payer(*authority_info.key)
+        .update_authority(*rooster_pda_info.key)
+        .spl_token_program(*spl_token_program_info.key)
+        .initialize_mint(true)
+        .update_authority_as_signer(true)
+        .build(create_args);
+
+    let instruction = match build_result {
+        Ok(create) => create.instruction(),
+        Err(err) => {
+            msg!("Error building create instruction: {:?}", err);
+            return Err(Crows::CreateBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        metadata_info.clone(),
+        edition_info.clone(),
+        mint_info.clone(),
+        rooster_pda_info.clone(),
+        authority_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        spl_token_program_info.clone(),
+    ];
+
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking create instruction: {:?}", err);
+        err
+    })?;
+
+    // mints the token into the associated token account of the rooster PDA
+
+    let mint_args = mpl_token_metadata::instruction::MintArgs::V1 {
+        amount: 1,
+        authorization_data: args.authorization_data,
+    };
+
+    let mut builder = MintBuilder::new();
+    builder
+        .token(*token_info.key)
+        .token_owner(*rooster_pda_info.key)
+        .metadata(*metadata_info.key)
+        .master_edition(*edition_info.key)
+        .token_record(*token_record_info.key)
+        .mint(*mint_info.key)
+        .authority(*rooster_pda_info.key)
+        .payer(*authority_info.key)
+        .spl_token_program(*spl_token_program_info.key)
+        .spl_ata_program(*spl_ata_program_info.key);
+
+    if let Some(rule_set) = rule_set {
+        builder
+            .authorization_rules(rule_set)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let instruction = match builder.build(mint_args) {
+        Ok(mint) => mint.instruction(),
+        Err(err) => {
+            msg!("Error building mint instruction: {:?}", err);
+            return Err(Crows::MintBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        token_info.clone(),
+        rooster_pda_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        token_record_info.clone(),
+        mint_info.clone(),
+        authority_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        spl_token_program_info.clone(),
+        spl_ata_program_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
+    ];
+
+    invoke_signed(&instruction, &account_infos, &[&signer_seeds]).map_err(|err| {
+        msg!("Error invoking mint instruction: {:?}", err);
+        err
+    })?;
+
+    rooster.deposits = rooster.deposits.saturating_add(1);
+    rooster.save(rooster_pda_info)
+}
+
+fn update_metadata(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: UpdateMetadataArgs,
+) -> ProgramResult {
+    msg!("Rooster: UpdateMetadata");
+
+    let UpdateMetadataAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        delegate_record: delegate_record_info,
+        token: token_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        remaining,
+    } = UpdateMetadataAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+    assert_multisig(&rooster, remaining)?;
+
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let update_args = match args {
+        UpdateMetadataArgs::AsUpdateAuthority {
+            data,
+            rule_set,
+            auth_data,
+        } => mpl_token_metadata::instruction::UpdateArgs::AsUpdateAuthorityV2 {
+            new_update_authority: None,
+            data,
+            primary_sale_happened: None,
+            is_mutable: None,
+            collection: Default::default(),
+            collection_details: Default::default(),
+            uses: Default::default(),
+            rule_set,
+            token_standard: None,
+            authorization_data: auth_data,
+        },
+        UpdateMetadataArgs::AsAuthorityItemDelegate {
+            primary_sale_happened,
+            is_mutable,
+            auth_data,
+        } => mpl_token_metadata::instruction::UpdateArgs::AsAuthorityItemDelegateV2 {
+            new_update_authority: None,
+            primary_sale_happened,
+            is_mutable,
+            token_standard: None,
+            authorization_data: auth_data,
+        },
+        UpdateMetadataArgs::AsProgrammableConfigDelegate {
+            rule_set,
+            auth_data,
+        } => mpl_token_metadata::instruction::UpdateArgs::AsProgrammableConfigDelegateV2 {
+            rule_set,
+            authorization_data: auth_data,
+        },
+    };
+
+    let mut builder = UpdateBuilder::new();
+    builder
+        .authority(*rooster_pda_info.key)
+        .mint(*mint_info.key)
+        .metadata(*metadata_info.key)
+        .edition(*edition_info.key)
+        .payer(*authority_info.key);
+
+    // absent optional accounts are replaced by the Token Metadata program
+    if *delegate_record_info.key != mpl_token_metadata::ID {
+        builder.delegate_record(*delegate_record_info.key);
+    }
+
+    if *token_info.key != mpl_token_metadata::ID {
+        builder.token(*token_info.key);
+    }
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let instruction = match builder.build(update_args) {
+        Ok(update) => update.instruction(),
+        Err(err) => {
+            msg!("Error building update instruction: {:?}", err);
+            return Err(Crows::UpdateBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        rooster_pda_info.clone(),
+        delegate_record_info.clone(),
+        token_info.clone(),
+        mint_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        authority_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking update instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn delegate_metadata(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: DelegateMetadataArgs,
+) -> ProgramResult {
+    msg!("Rooster: DelegateMetadata");
+
+    let MetadataDelegateAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        delegate: delegate_info,
+        delegate_record: delegate_record_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        remaining,
+    } = MetadataDelegateAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_DELEGATE)?;
+    assert_multisig(&rooster, remaining)?;
+
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let authorization_data = args.auth_data;
+    let delegate_args = match args.role {
+        MetadataDelegate::Collection => {
+            mpl_token_metadata::instruction::DelegateArgs::CollectionV1 { authorization_data }
+        }
+        MetadataDelegate::AuthorityItem => {
+            mpl_token_metadata::instruction::DelegateArgs::AuthorityItemV1 { authorization_data }
+        }
+        MetadataDelegate::ProgrammableConfig => {
+            mpl_token_metadata::instruction::DelegateArgs::ProgrammableConfigV1 {
+                authorization_data,
+            }
+        }
+    };
+
+    // the rooster PDA approves the delegate as the update authority of the asset
+    let mut builder = DelegateBuilder::new();
+    builder
+        .delegate_record(*delegate_record_info.key)
+        .delegate(*delegate_info.key)
+        .metadata(*metadata_info.key)
+        .master_edition(*edition_info.key)
+        .mint(*mint_info.key)
+        .authority(*rooster_pda_info.key)
+        .payer(*authority_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let instruction = match builder.build(delegate_args) {
+        Ok(delegate) => delegate.instruction(),
+        Err(err) => {
+            msg!("Error building delegate instruction: {:?}", err);
+            return Err(Crows::DelegateBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        delegate_record_info.clone(),
+        delegate_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        mint_info.clone(),
+        rooster_pda_info.clone(),
+        authority_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking delegate metadata instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn revoke_metadata(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: RevokeMetadataArgs,
+) -> ProgramResult {
+    msg!("Rooster: RevokeMetadata");
+
+    let MetadataDelegateAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        delegate: delegate_info,
+        delegate_record: delegate_record_info,
+        mint: mint_info,
+        metadata: metadata_info,
+        edition: edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        authorization_rules_program: mpl_token_auth_rules_program_info,
+        authorization_rules: rule_set_info,
+        ..
+    } = MetadataDelegateAccounts::context(accounts)?;
+
+    // revoking is not subject to the pause flags, so delegates can be cleared while paused
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+
+    assert_metadata_derivation(metadata_info, mint_info.key)?;
+    assert_edition_derivation(edition_info, mint_info.key)?;
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(
+        mpl_token_auth_rules_program_info,
+        &MPL_TOKEN_AUTH_RULES_PROGRAM_ID,
+    )?;
+    assert_rule_set(rule_set_info)?;
+
+    let revoke_args = match args.role {
+        MetadataDelegate::Collection => mpl_token_metadata::instruction::RevokeArgs::CollectionV1,
+        MetadataDelegate::AuthorityItem => {
+            mpl_token_metadata::instruction::RevokeArgs::AuthorityItemV1
+        }
+        MetadataDelegate::ProgrammableConfig => {
+            mpl_token_metadata::instruction::RevokeArgs::ProgrammableConfigV1
+        }
+    };
+
+    let mut builder = RevokeBuilder::new();
+    builder
+        .delegate_record(*delegate_record_info.key)
+        .delegate(*delegate_info.key)
+        .metadata(*metadata_info.key)
+        .master_edition(*edition_info.key)
+        .mint(*mint_info.key)
+        .authority(*rooster_pda_info.key)
+        .payer(*authority_info.key);
+
+    if *rule_set_info.key != mpl_token_metadata::ID {
+        builder
+            .authorization_rules(*rule_set_info.key)
+            .authorization_rules_program(*mpl_token_auth_rules_program_info.key);
+    }
+
+    let instruction = match builder.build(revoke_args) {
+        Ok(revoke) => revoke.instruction(),
+        Err(err) => {
+            msg!("Error building revoke instruction: {:?}", err);
+            return Err(Crows::RevokeBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        delegate_record_info.clone(),
+        delegate_info.clone(),
+        metadata_info.clone(),
+        edition_info.clone(),
+        mint_info.clone(),
+        rooster_pda_info.clone(),
+        authority_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        mpl_token_auth_rules_program_info.clone(),
+        rule_set_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking revoke metadata instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn verify(_program_id: &Pubkey, accounts: &[AccountInfo], args: VerificationArgs) -> ProgramResult {
+    msg!("Rooster: Verify");
+
+    let VerificationAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        metadata: metadata_info,
+        collection_mint: collection_mint_info,
+        collection_metadata: collection_metadata_info,
+        collection_master_edition: collection_master_edition_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        remaining,
+    } = VerificationAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+    assert_multisig(&rooster, remaining)?;
+
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+
+    let mut builder = VerifyBuilder::new();
+    builder
+        .authority(*rooster_pda_info.key)
+        .metadata(*metadata_info.key);
+
+    let verification_args = match args {
+        VerificationArgs::Creator => mpl_token_metadata::instruction::VerificationArgs::CreatorV1,
+        VerificationArgs::Collection => {
+            assert_metadata_derivation(collection_metadata_info, collection_mint_info.key)?;
+            assert_edition_derivation(collection_master_edition_info, collection_mint_info.key)?;
+
+            // sized collections are resized by Token Metadata through the collection metadata
+            builder
+                .collection_mint(*collection_mint_info.key)
+                .collection_metadata(*collection_metadata_info.key)
+                .collection_master_edition(*collection_master_edition_info.key);
+            mpl_token_metadata::instruction::VerificationArgs::CollectionV1
+        }
+    };
+
+    let instruction = match builder.build(verification_args) {
+        Ok(verify) => verify.instruction(),
+        Err(err) => {
+            msg!("Error building verify instruction: {:?}", err);
+            return Err(Crows::VerifyBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        rooster_pda_info.clone(),
+        metadata_info.clone(),
+        collection_mint_info.clone(),
+        collection_metadata_info.clone(),
+        collection_master_edition_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking verify instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn unverify(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: VerificationArgs,
+) -> ProgramResult {
+    msg!("Rooster: Unverify");
+
+    let VerificationAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        metadata: metadata_info,
+        collection_mint: collection_mint_info,
+        collection_metadata: collection_metadata_info,
+        token_metadata_program: token_metadata_program_info,
+        system_program: system_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        remaining,
+        ..
+    } = VerificationAccounts::context(accounts)?;
+
+    let rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+    assert_multisig(&rooster, remaining)?;
+
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+
+    let mut builder = UnverifyBuilder::new();
+    builder
+        .authority(*rooster_pda_info.key)
+        .metadata(*metadata_info.key);
+
+    let verification_args = match args {
+        VerificationArgs::Creator => mpl_token_metadata::instruction::VerificationArgs::CreatorV1,
+        VerificationArgs::Collection => {
+            assert_metadata_derivation(collection_metadata_info, collection_mint_info.key)?;
+
+            builder
+                .collection_mint(*collection_mint_info.key)
+                .collection_metadata(*collection_metadata_info.key);
+            mpl_token_metadata::instruction::VerificationArgs::CollectionV1
+        }
+    };
+
+    let instruction = match builder.build(verification_args) {
+        Ok(unverify) => unverify.instruction(),
+        Err(err) => {
+            msg!("Error building unverify instruction: {:?}", err);
+            return Err(Crows::UnverifyBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        rooster_pda_info.clone(),
+        metadata_info.clone(),
+        collection_mint_info.clone(),
+        collection_metadata_info.clone(),
+        token_metadata_program_info.clone(),
+        system_program_info.clone(),
+        sysvar_instructions_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking unverify instruction: {:?}", err);
+        err
+    })?;
+
+    Ok(())
+}
+
+fn print_edition(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    args: PrintEditionArgs,
+) -> ProgramResult {
+    msg!("Rooster: PrintEdition");
+
+    let PrintEditionAccounts {
+        authority: authority_info,
+        rooster_pda: rooster_pda_info,
+        edition_mint: edition_mint_info,
+        edition_metadata: edition_metadata_info,
+        edition: edition_info,
+        edition_token_owner: edition_token_owner_info,
+        edition_token: edition_token_info,
+        edition_token_record: edition_token_record_info,
+        master_edition: master_edition_info,
+        edition_marker: edition_marker_info,
+        master_token: master_token_info,
+        master_metadata: master_metadata_info,
+        update_authority: update_authority_info,
+        token_metadata_program: token_metadata_program_info,
+        spl_token_program: spl_token_program_info,
+        spl_ata_program: spl_ata_program_info,
+        sysvar_instructions: sysvar_instructions_info,
+        system_program: system_program_info,
+        remaining,
+    } = PrintEditionAccounts::context(accounts)?;
+
+    let mut rooster = Rooster::from_account_info(rooster_pda_info)?;
+    assert_rooster_authority(&rooster, authority_info)?;
+    assert_rooster_pda(rooster_pda_info, &rooster)?;
+    assert_not_paused(&rooster, PAUSE_ALL)?;
+    assert_multisig(&rooster, remaining)?;
+
+    let master_token = assert_token_account_owner(master_token_info, rooster_pda_info.key)?;
+    assert_metadata_derivation(master_metadata_info, &master_token.mint)?;
+    assert_edition_derivation(master_edition_info, &master_token.mint)?;
+
+    let (edition_marker, _) = find_edition_marker_account(&master_token.mint, args.edition);
+
+    if edition_marker != *edition_marker_info.key {
+        return Err(Crows::EditionMismatch.into());
+    }
+
+    assert_metadata_derivation(edition_metadata_info, edition_mint_info.key)?;
+    assert_edition_derivation(edition_info, edition_mint_info.key)?;
+    assert_associated_token_account(
+        edition_token_info,
+        edition_token_owner_info.key,
+        edition_mint_info.key,
+    )?;
+
+    if *edition_token_record_info.key != mpl_token_metadata::ID {
+        assert_token_record_derivation(
+            edition_token_record_info,
+            edition_mint_info.key,
+            edition_token_info.key,
+        )?;
+    }
+
+    assert_program_id(token_metadata_program_info, &mpl_token_metadata::ID)?;
+    assert_program_id(system_program_info, &solana_program::system_program::ID)?;
+    assert_sysvar_instructions(sysvar_instructions_info)?;
+    assert_program_id(spl_token_program_info, &spl_token::ID)?;
+    assert_program_id(spl_ata_program_info, &SPL_ATA_TOKEN_PROGRAM_ID)?;
+
+    // the rooster PDA proves ownership of the master edition token
+    let mut builder = PrintBuilder::new();
+    builder
+        .edition_metadata(*edition_metadata_info.key)
+        .edition(*edition_info.key)
+        .edition_mint(*edition_mint_info.key)
+        .edition_token_account_owner(*edition_token_owner_info.key)
+        .edition_token_account(*edition_token_info.key)
+        .edition_mint_authority(*authority_info.key)
+        .master_edition(*master_edition_info.key)
+        .edition_marker_pda(*edition_marker_info.key)
+        .payer(*authority_info.key)
+        .master_token_account_owner(*rooster_pda_info.key)
+        .master_token_account(*master_token_info.key)
+        .master_metadata(*master_metadata_info.key)
+        .update_authority(*update_authority_info.key)
+        .spl_token_program(*spl_token_program_info.key)
+        .spl_ata_program(*spl_ata_program_info.key)
+        .initialize_mint(true);
+
+    // prints of legacy master editions have no token record
+    if *edition_token_record_info.key != mpl_token_metadata::ID {
+        builder.edition_token_record(*edition_token_record_info.key);
+    }
+
+    let print_args = mpl_token_metadata::instruction::PrintArgs::V1 {
+        edition: args.edition,
+    };
+
+    let instruction = match builder.build(print_args) {
+        Ok(print) => print.instruction(),
+        Err(err) => {
+            msg!("Error building print instruction: {:?}", err);
+            return Err(Crows::PrintBuilderFailed.into());
+        }
+    };
+
+    let account_infos = [
+        edition_metadata_info.clone(),
+        edition_info.clone(),
+        edition_mint_info.clone(),
+        edition_token_owner_info.clone(),
+        edition_token_info.clone(),
+        authority_info.clone(),
+        edition_token_record_info.clone(),
+        master_edition_info.clone(),
+        edition_marker_info.clone(),
+        rooster_pda_info.clone(),
+        master_token_info.clone(),
+        master_metadata_info.clone(),
+        update_authority_info.clone(),
+        token_metadata_program_info.clone(),
+        spl_token_program_info.clone(),
+        spl_ata_program_info.clone(),
+        sysvar_instructions_info.clone(),
+        system_program_info.clone(),
+    ];
+
+    let rooster_seeds = rooster.seeds();
+    invoke_signed(&instruction, &account_infos, &[&rooster_seeds.seeds()]).map_err(|err| {
+        msg!("Error invoking print instruction: {:?}", err);
+        err
+    })?;
+
+    if edition_token_owner_info.key == rooster_pda_info.key {
+        rooster.deposits = rooster.deposits.saturating_add(1);
+        rooster.save(rooster_pda_info)?;
+    }
+
+    Ok(())
+}
+
+fn hash_auth_data(auth_data: &AuthorizationData) -> Result<[u8; 32], ProgramError> {
+    Ok(hash(&auth_data.try_to_vec()?).to_bytes())
+}
+
+fn close_withdraw_request(
+    withdraw_request_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+) -> ProgramResult {
+    let recipient_lamports = recipient_info
+        .lamports()
+        .checked_add(withdraw_request_info.lamports())
+        .ok_or(Crows::NumericalOverflow)?;
+
+    **recipient_info.try_borrow_mut_lamports()? = recipient_lamports;
+    **withdraw_request_info.try_borrow_mut_lamports()? = 0;
+    withdraw_request_info.try_borrow_mut_data()?.fill(0);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pda::find_indexed_rooster_pda, state::PAUSE_LOCK};
+
+    use super::*;
+
+    struct Fixture {
+        authority: Pubkey,
+        guardian: Pubkey,
+        rooster_pda: Pubkey,
+        data: Vec<u8>,
+    }
+
+    impl Fixture {
+        fn new(pause_flags: u8) -> Self {
+            let authority = Pubkey::new_unique();
+            let guardian = Pubkey::new_unique();
+            let (rooster_pda, bump) = find_indexed_rooster_pda(&authority, None);
+
+            let mut rooster = Rooster::new(bump, authority, None, 0);
+            rooster.guardian = Some(guardian);
+            rooster.pause_flags = pause_flags;
+
+            let mut data = rooster.try_to_vec().unwrap();
+            data.resize(ROOSTER_LEN, 0);
+
+            Self {
+                authority,
+                guardian,
+                rooster_pda,
+                data,
+            }
+        }
+
+        fn set_pause(&mut self, signer: Pubkey, pause_flags: u8) -> ProgramResult {
+            let (mut signer_lamports, mut rooster_lamports) = (0, 0);
+            let mut signer_data = [];
+            let accounts = [
+                AccountInfo::new(
+                    &signer,
+                    true,
+                    false,
+                    &mut signer_lamports,
+                    &mut signer_data,
+                    &solana_program::system_program::ID,
+                    false,
+                    0,
+                ),
+                AccountInfo::new(
+                    &self.rooster_pda,
+                    false,
+                    true,
+                    &mut rooster_lamports,
+                    &mut self.data,
+                    &crate::ID,
+                    false,
+                    0,
+                ),
+            ];
+
+            set_pause(&crate::ID, &accounts, SetPauseArgs { pause_flags })
+        }
+
+        fn pause_flags(&self) -> u8 {
+            Rooster::deserialize(&mut self.data.as_slice())
+                .unwrap()
+                .pause_flags
+        }
+    }
+
+    #[test]
+    fn set_pause_rejects_unknown_flags() {
+        let mut fixture = Fixture::new(0);
+        let authority = fixture.authority;
+
+        assert_eq!(
+            fixture.set_pause(authority, 1 << 4),
+            Err(Crows::InvalidPauseFlags.into())
+        );
+        assert_eq!(fixture.pause_flags(), 0);
+    }
+
+    #[test]
+    fn guardian_can_only_add_pause_flags() {
+        let mut fixture = Fixture::new(PAUSE_WITHDRAW);
+        let guardian = fixture.guardian;
+
+        assert_eq!(
+            fixture.set_pause(guardian, PAUSE_WITHDRAW | PAUSE_LOCK),
+            Ok(())
+        );
+        assert_eq!(fixture.pause_flags(), PAUSE_WITHDRAW | PAUSE_LOCK);
+
+        assert_eq!(
+            fixture.set_pause(guardian, PAUSE_LOCK),
+            Err(Crows::InvalidAuthority.into())
+        );
+        assert_eq!(
+            fixture.set_pause(guardian, 0),
+            Err(Crows::InvalidAuthority.into())
+        );
+        assert_eq!(fixture.pause_flags(), PAUSE_WITHDRAW | PAUSE_LOCK);
+    }
+
+    #[test]
+    fn authority_can_clear_pause_flags() {
+        let mut fixture = Fixture::new(PAUSE_ALL);
+        let authority = fixture.authority;
+
+        assert_eq!(fixture.set_pause(authority, 0), Ok(()));
+        assert_eq!(fixture.pause_flags(), 0);
+    }
+
+    #[test]
+    fn set_pause_rejects_other_signers() {
+        let mut fixture = Fixture::new(0);
+
+        assert_eq!(
+            fixture.set_pause(Pubkey::new_unique(), PAUSE_ALL),
+            Err(Crows::InvalidAuthority.into())
+        );
+    }
+}
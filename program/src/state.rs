@@ -1,14 +1,313 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::ShankAccount;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    program_memory::sol_memcpy, pubkey::Pubkey,
+};
+
+use crate::{error::Crows, pda::RoosterSeeds};
+
+/// Current version of the `Rooster` account layout.
+pub const ROOSTER_VERSION: u8 = 3;
+
+/// Maximum number of keys in the multisig signer set of a rooster.
+pub const MAX_MULTISIG_SIGNERS: usize = 8;
+
+/// Size of a version 1 `Rooster` account, which only stored the PDA bump.
+pub const ROOSTER_V1_LEN: usize = 1;
+
+/// Allocated size of a `Rooster` account. Bytes after the serialized fields are zero
+/// and reserved, so new fields can be appended without resizing the account.
+pub const ROOSTER_LEN: usize = 1 // key
+    + 1 // version
+    + 1 // bump
+    + 32 // seed
+    + 8 // created_slot
+    + 8 // deposits
+    + 8 // withdrawals
+    + 8 // delegations
+    + 8 // delegate_transfers
+    + 9 // index
+    + 32 // authority
+    + 33 // pending_authority
+    + 1 // threshold
+    + 4 + 32 * MAX_MULTISIG_SIGNERS // signers
+    + 8 // withdraw_delay
+    + 1 // pause_flags
+    + 33 // guardian
+    + 8 // queued_withdrawals
+    + 52; // reserved
+
+/// Pauses withdrawals (direct, queued and executed).
+pub const PAUSE_WITHDRAW: u8 = 1 << 0;
+/// Pauses delegations and transfers as a delegate.
+pub const PAUSE_DELEGATE: u8 = 1 << 1;
+/// Pauses locks, programmable or not.
+pub const PAUSE_LOCK: u8 = 1 << 2;
+/// Pauses unlocks, programmable or not.
+pub const PAUSE_UNLOCK: u8 = 1 << 3;
+/// Pauses every operation of the rooster, including deposits.
+pub const PAUSE_ALL: u8 = 1 << 7;
+
+/// Pause flags known to the program.
+pub const PAUSE_FLAGS: u8 = PAUSE_WITHDRAW | PAUSE_DELEGATE | PAUSE_LOCK | PAUSE_UNLOCK | PAUSE_ALL;
+
+/// Size of a `WithdrawRequest` account.
+pub const WITHDRAW_REQUEST_LEN: usize = 1 // key
+    + 1 // bump
+    + 32 // rooster
+    + 32 // mint
+    + 32 // destination_owner
+    + 32 // destination
+    + 32 // auth_data_hash
+    + 8 // queued_at
+    + 8; // executable_at
+
+#[repr(C)]
+#[derive(Clone, Copy, BorshSerialize, BorshDeserialize, Debug, PartialEq, Eq)]
+pub enum Key {
+    Uninitialized,
+    Rooster,
+    WithdrawRequest,
+}
 
 #[repr(C)]
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
 pub struct Rooster {
-    bump: u8,
+    /// Account discriminator.
+    pub key: Key,
+    /// Layout version of the account.
+    pub version: u8,
+    /// Canonical bump of the Rooster PDA.
+    pub bump: u8,
+    /// Key used in the PDA seeds: the authority that created the rooster. Version 2
+    /// stored the (then fixed) authority in this position.
+    pub seed: Pubkey,
+    /// Slot in which the rooster was created (or migrated from version 1).
+    pub created_slot: u64,
+    /// Number of deposits into the rooster.
+    pub deposits: u64,
+    /// Number of withdrawals from the rooster.
+    pub withdrawals: u64,
+    /// Number of delegates approved by the rooster.
+    pub delegations: u64,
+    /// Number of transfers performed by the rooster as a delegate.
+    pub delegate_transfers: u64,
+    /// Vault index used in the PDA seeds, `None` for the authority's unindexed rooster.
+    pub index: Option<u64>,
+    /// Authority allowed to operate the rooster.
+    pub authority: Pubkey,
+    /// Authority proposed by the current authority, which must accept it.
+    pub pending_authority: Option<Pubkey>,
+    /// Number of multisig signers required to approve withdrawals and delegations, `0` when
+    /// the rooster is not in multisig mode.
+    pub threshold: u8,
+    /// Keys allowed to approve withdrawals and delegations in multisig mode.
+    pub signers: Vec<Pubkey>,
+    /// Delay in seconds between queueing and executing a withdrawal, `0` when withdrawals
+    /// are not timelocked.
+    pub withdraw_delay: i64,
+    /// Operations currently paused, as a set of `PAUSE_*` flags.
+    pub pause_flags: u8,
+    /// Key allowed to pause the rooster besides the authority.
+    pub guardian: Option<Pubkey>,
+    /// Number of withdrawals queued and not yet executed or cancelled.
+    pub queued_withdrawals: u64,
 }
 
 impl Rooster {
-    pub fn new(bump: u8) -> Self {
-        Self { bump }
+    pub fn new(bump: u8, authority: Pubkey, index: Option<u64>, created_slot: u64) -> Self {
+        Self {
+            key: Key::Rooster,
+            version: ROOSTER_VERSION,
+            bump,
+            seed: authority,
+            created_slot,
+            deposits: 0,
+            withdrawals: 0,
+            delegations: 0,
+            delegate_transfers: 0,
+            index,
+            authority,
+            pending_authority: None,
+            threshold: 0,
+            signers: Vec::new(),
+            withdraw_delay: 0,
+            pause_flags: 0,
+            guardian: None,
+            queued_withdrawals: 0,
+        }
+    }
+
+    pub fn seeds(&self) -> RoosterSeeds {
+        RoosterSeeds::new(&self.seed, self.index).with_bump(self.bump)
+    }
+
+    /// Whether the operation guarded by `flag` is paused, either by its own flag or by
+    /// `PAUSE_ALL`.
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & (flag | PAUSE_ALL) != 0
+    }
+
+    /// Deserializes a `Rooster` account, rejecting accounts that are not owned by the
+    /// Rooster program, are not a `Rooster` or still use an older layout.
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        let rooster = Self::from_account_info_unversioned(account_info)?;
+
+        if rooster.version != ROOSTER_VERSION {
+            return Err(Crows::RoosterNeedsMigration.into());
+        }
+
+        Ok(rooster)
+    }
+
+    /// Deserializes a `Rooster` account of any version since 2, for migrations. Fields
+    /// introduced after the account version hold their zero value.
+    pub fn from_account_info_unversioned(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        if *account_info.owner != crate::ID {
+            return Err(Crows::IncorrectOwner.into());
+        }
+
+        let data = account_info.try_borrow_data()?;
+
+        if data.len() == ROOSTER_V1_LEN {
+            return Err(Crows::RoosterNeedsMigration.into());
+        }
+
+        let rooster = Self::deserialize(&mut data.as_ref())?;
+
+        if rooster.key != Key::Rooster {
+            return Err(Crows::InvalidAccountKey.into());
+        }
+
+        Ok(rooster)
+    }
+
+    /// Serializes the rooster into the account and zeroes the bytes after it, which may hold
+    /// a longer previous value of a variable-length field (signers, optional keys).
+    pub fn save(&self, account_info: &AccountInfo) -> ProgramResult {
+        let serialized_rooster = self.try_to_vec()?;
+        let mut data = account_info.try_borrow_mut_data()?;
+        sol_memcpy(
+            &mut data,
+            serialized_rooster.as_slice(),
+            serialized_rooster.len(),
+        );
+        data[serialized_rooster.len()..].fill(0);
+
+        Ok(())
+    }
+}
+
+/// Version 1 layout of the `Rooster` account.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug)]
+pub struct RoosterV1 {
+    pub bump: u8,
+}
+
+impl RoosterV1 {
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        if *account_info.owner != crate::ID {
+            return Err(Crows::IncorrectOwner.into());
+        }
+
+        let data = account_info.try_borrow_data()?;
+
+        if data.len() != ROOSTER_V1_LEN {
+            return Err(Crows::InvalidAccountKey.into());
+        }
+
+        Ok(Self::try_from_slice(&data)?)
+    }
+}
+
+/// Withdrawal queued on a timelocked rooster, executable once `executable_at` is reached.
+#[repr(C)]
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, ShankAccount)]
+pub struct WithdrawRequest {
+    /// Account discriminator.
+    pub key: Key,
+    /// Canonical bump of the withdraw request PDA.
+    pub bump: u8,
+    /// Rooster PDA the token is withdrawn from.
+    pub rooster: Pubkey,
+    /// Mint of the withdrawn token.
+    pub mint: Pubkey,
+    /// Owner of the destination token account.
+    pub destination_owner: Pubkey,
+    /// Destination token account.
+    pub destination: Pubkey,
+    /// Hash of the serialized `AuthorizationData` the withdrawal must be executed with.
+    pub auth_data_hash: [u8; 32],
+    /// Unix timestamp at which the withdrawal was queued.
+    pub queued_at: i64,
+    /// Unix timestamp from which the withdrawal can be executed.
+    pub executable_at: i64,
+}
+
+impl WithdrawRequest {
+    pub fn from_account_info(account_info: &AccountInfo) -> Result<Self, ProgramError> {
+        if *account_info.owner != crate::ID {
+            return Err(Crows::IncorrectOwner.into());
+        }
+
+        let data = account_info.try_borrow_data()?;
+        let withdraw_request = Self::deserialize(&mut data.as_ref())?;
+
+        if withdraw_request.key != Key::WithdrawRequest {
+            return Err(Crows::InvalidAccountKey.into());
+        }
+
+        Ok(withdraw_request)
+    }
+
+    pub fn save(&self, account_info: &AccountInfo) -> ProgramResult {
+        let serialized_request = self.try_to_vec()?;
+        sol_memcpy(
+            &mut account_info.try_borrow_mut_data()?,
+            serialized_request.as_slice(),
+            serialized_request.len(),
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pause_all_covers_every_flag() {
+        let mut rooster = Rooster::new(255, Pubkey::new_unique(), None, 0);
+
+        for flag in [
+            PAUSE_WITHDRAW,
+            PAUSE_DELEGATE,
+            PAUSE_LOCK,
+            PAUSE_UNLOCK,
+            PAUSE_ALL,
+        ] {
+            assert!(!rooster.is_paused(flag));
+        }
+
+        rooster.pause_flags = PAUSE_ALL;
+
+        for flag in [
+            PAUSE_WITHDRAW,
+            PAUSE_DELEGATE,
+            PAUSE_LOCK,
+            PAUSE_UNLOCK,
+            PAUSE_ALL,
+        ] {
+            assert!(rooster.is_paused(flag));
+        }
+
+        rooster.pause_flags = PAUSE_LOCK;
+
+        assert!(rooster.is_paused(PAUSE_LOCK));
+        assert!(!rooster.is_paused(PAUSE_UNLOCK));
+        assert!(!rooster.is_paused(PAUSE_ALL));
     }
 }